@@ -1,13 +1,17 @@
 using System;
+using System.Collections.Concurrent;
 using System.Collections.Generic;
 using System.IO;
 using System.Text.Json;
 using System.Linq;
+using System.Linq.Expressions;
 using System.Reflection;
 using System.Runtime.InteropServices;
 using System.Runtime.CompilerServices;
 using System.Runtime.Loader;
 using System.Text;
+using Microsoft.CodeAnalysis.CSharp;
+using Roslyn = Microsoft.CodeAnalysis;
 
 class Field {
     public string Name { get; set; } = default;
@@ -23,6 +27,93 @@ class Property {
     public bool CanWrite { get; set; }
 }
 
+// Named *Description so they don't clash with System.Reflection's TypeInfo, MethodInfo, etc.
+class TypeDescription {
+    public string FullName { get; set; } = default;
+    public string Name { get; set; } = default;
+    public string? Namespace { get; set; }
+    public string? BaseType { get; set; }
+    public string[] Interfaces { get; set; } = Array.Empty<string>();
+    public bool IsAbstract { get; set; }
+    public bool IsInterface { get; set; }
+    public bool IsValueType { get; set; }
+    public bool IsEnum { get; set; }
+    public string[] GenericParameters { get; set; } = Array.Empty<string>();
+    public FieldDescription[] Fields { get; set; } = Array.Empty<FieldDescription>();
+    public PropertyDescription[] Properties { get; set; } = Array.Empty<PropertyDescription>();
+    public MethodDescription[] Constructors { get; set; } = Array.Empty<MethodDescription>();
+    public MethodDescription[] Methods { get; set; } = Array.Empty<MethodDescription>();
+    public AttributeDescription[] Attributes { get; set; } = Array.Empty<AttributeDescription>();
+}
+
+class FieldDescription {
+    public string Name { get; set; } = default;
+    public string Type { get; set; } = default;
+    public bool IsStatic { get; set; }
+    public bool IsPublic { get; set; }
+    public bool IsReadOnly { get; set; }
+    public AttributeDescription[] Attributes { get; set; } = Array.Empty<AttributeDescription>();
+}
+
+class PropertyDescription {
+    public string Name { get; set; } = default;
+    public string Type { get; set; } = default;
+    public bool IsStatic { get; set; }
+    public bool CanRead { get; set; }
+    public bool CanWrite { get; set; }
+    public AttributeDescription[] Attributes { get; set; } = Array.Empty<AttributeDescription>();
+}
+
+class MethodDescription {
+    public string Name { get; set; } = default;
+    public string ReturnType { get; set; } = default;
+    public bool IsStatic { get; set; }
+    public bool IsPublic { get; set; }
+    public bool IsAbstract { get; set; }
+    public bool IsVirtual { get; set; }
+    public string[] GenericParameters { get; set; } = Array.Empty<string>();
+    public ParameterDescription[] Parameters { get; set; } = Array.Empty<ParameterDescription>();
+    public AttributeDescription[] Attributes { get; set; } = Array.Empty<AttributeDescription>();
+}
+
+class ParameterDescription {
+    public string? Name { get; set; }
+    public string Type { get; set; } = default;
+    public bool IsValueType { get; set; }
+    // Marshalled size of non-primitive value types, 0 otherwise
+    public int Size { get; set; }
+    // Marshalled size of the elements of arrays of non-primitive value types, 0 otherwise
+    public int ElementSize { get; set; }
+    // Ref structs like `Span<T>` can't be boxed or read by the compiled thunks
+    public bool IsByRefLike { get; set; }
+    public bool IsOut { get; set; }
+    public bool IsOptional { get; set; }
+}
+
+class AttributeDescription {
+    public string Type { get; set; } = default;
+    public string?[] Arguments { get; set; } = Array.Empty<string?>();
+}
+
+class CompileRequest {
+    public string AssemblyName { get; set; } = "Scripts";
+    public string[] Sources { get; set; } = Array.Empty<string>();
+    public string[] References { get; set; } = Array.Empty<string>();
+    public bool Debug { get; set; }
+    public string Nullable { get; set; } = "enable";
+    public string? LangVersion { get; set; }
+    public string[] Defines { get; set; } = Array.Empty<string>();
+}
+
+class CompileDiagnostic {
+    public string Filename { get; set; } = default;
+    public int Line { get; set; }
+    public int Column { get; set; }
+    public string Severity { get; set; } = default;
+    public string Code { get; set; } = default;
+    public string Message { get; set; } = default;
+}
+
 public enum Error {
     ClassNotFound = 1,
     MethodNotFound = 2,
@@ -35,6 +126,11 @@ public enum Error {
     PathNotFound = 9,
     AssemblyNotLoaded = 10,
     ClassNotRegistered = 11,
+    NotBlittable = 12,
+    SizeMismatch = 13,
+    InvalidInstance = 14,
+    InvocationFailed = 15,
+    CompilationFailed = 16,
 }
 
 public sealed class Scope : AssemblyLoadContext
@@ -47,6 +143,30 @@ public sealed class Scope : AssemblyLoadContext
     }
 }
 
+// A field or property resolved once, read and written through compiled delegates instead of reflection
+public sealed class Accessor
+{
+    public MemberInfo Member = default!;
+    public Type ValueType = default!;
+    // Size of blittable values, -1 when they have to be serialized
+    public int Size;
+    public Func<object, object?>? Get;
+    // Blittable values are copied to and from the host's buffer without boxing
+    public Action<object, IntPtr>? GetInto;
+    public Action<object, IntPtr>? SetFrom;
+    public Action<object, object?>? Set;
+    // Reported when there is no setter, `ReadonlyField` or `MissingSetter`
+    public Error SetError;
+}
+
+// A resolved method with a thunk that reads its arguments straight from the host's `void**`
+public sealed class Invoker
+{
+    public MethodInfo Method = default!;
+    // `null` when the signature can't be compiled, e.g. `ref` parameters, the method is invoked with reflection instead
+    public Action<object?, IntPtr>? Thunk;
+}
+
 public class Host
 {
     static string ReadUtf8Z(IntPtr p)
@@ -167,6 +287,113 @@ public class Host
         GC.Collect(); GC.WaitForPendingFinalizers(); GC.Collect();
     }
 
+    // ----- COMPILE -----
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void CompileDelegate(IntPtr requestUtf8Z, out IntPtr bytes, out int length, out IntPtr diagnostics, out int error);
+    public static void Compile(IntPtr request, out IntPtr bytes, out int length, out IntPtr diagnostics, out int error)
+    {
+        bytes = IntPtr.Zero;
+        length = 0;
+        diagnostics = IntPtr.Zero;
+        error = 0;
+
+        try
+        {
+            var req = JsonSerializer.Deserialize<CompileRequest>(ReadUtf8Z(request));
+            if (req == null) {
+                error = (int)Error.MissingRequiredArgument;
+                return;
+            }
+
+            // The SDK default is used when not set, like in the generated projects
+            var languageVersion = LanguageVersion.Default;
+            if (req.LangVersion != null && !LanguageVersionFacts.TryParse(req.LangVersion, out languageVersion)) {
+                error = (int)Error.CompilationFailed;
+                return;
+            }
+            var parseOptions = new CSharpParseOptions(languageVersion, preprocessorSymbols: CompileSymbols(req));
+            var trees = new List<Roslyn.SyntaxTree>();
+            foreach (var source in req.Sources)
+            {
+                if (!File.Exists(source)) {
+                    error = (int)Error.PathNotFound;
+                    return;
+                }
+                trees.Add(CSharpSyntaxTree.ParseText(File.ReadAllText(source), parseOptions, path: source, encoding: Encoding.UTF8));
+            }
+
+            // Framework assemblies the hosted runtime was started with
+            var platform = ((string?)AppContext.GetData("TRUSTED_PLATFORM_ASSEMBLIES") ?? string.Empty)
+                .Split(Path.PathSeparator, StringSplitOptions.RemoveEmptyEntries);
+            var references = platform
+                .Concat(req.References)
+                .Distinct()
+                .Select(p => (Roslyn.MetadataReference)Roslyn.MetadataReference.CreateFromFile(p));
+
+            var options = new CSharpCompilationOptions(
+                Roslyn.OutputKind.DynamicallyLinkedLibrary,
+                optimizationLevel: req.Debug ? Roslyn.OptimizationLevel.Debug : Roslyn.OptimizationLevel.Release,
+                nullableContextOptions: Enum.Parse<Roslyn.NullableContextOptions>(req.Nullable, ignoreCase: true),
+                allowUnsafe: true
+            );
+            var compilation = CSharpCompilation.Create(req.AssemblyName, trees, references, options);
+
+            using var ms = new MemoryStream();
+            var emit = compilation.Emit(ms);
+
+            var diags = emit.Diagnostics
+                .Where(d => d.Severity == Roslyn.DiagnosticSeverity.Warning || d.Severity == Roslyn.DiagnosticSeverity.Error)
+                .Select(d => {
+                    var span = d.Location.GetLineSpan();
+                    return new CompileDiagnostic {
+                        Filename = string.IsNullOrEmpty(span.Path) ? req.AssemblyName : span.Path,
+                        Line = span.StartLinePosition.Line + 1,
+                        Column = span.StartLinePosition.Character + 1,
+                        Severity = d.Severity == Roslyn.DiagnosticSeverity.Error ? "Error" : "Warning",
+                        Code = d.Id,
+                        Message = d.GetMessage(),
+                    };
+                })
+                .ToList();
+
+            byte[] payload = Encoding.UTF8.GetBytes(JsonSerializer.Serialize(diags));
+            diagnostics = Marshal.AllocHGlobal(payload.Length + 1);
+            Marshal.Copy(payload, 0, diagnostics, payload.Length);
+            Marshal.WriteByte(diagnostics, payload.Length, 0);
+
+            if (!emit.Success) return;
+
+            var image = ms.ToArray();
+            length = image.Length;
+            bytes = Marshal.AllocHGlobal(image.Length);
+            Marshal.Copy(image, 0, bytes, image.Length);
+        }
+        catch
+        {
+            // Unreadable sources or references, or a malformed request
+            if (diagnostics != IntPtr.Zero) Marshal.FreeHGlobal(diagnostics);
+            if (bytes != IntPtr.Zero) Marshal.FreeHGlobal(bytes);
+            bytes = IntPtr.Zero;
+            length = 0;
+            diagnostics = IntPtr.Zero;
+            error = (int)Error.CompilationFailed;
+        }
+    }
+
+    // Symbols MSBuild defines for the configuration and target framework, followed by the project defines
+    static IEnumerable<string> CompileSymbols(CompileRequest req)
+    {
+        yield return "TRACE";
+        yield return req.Debug ? "DEBUG" : "RELEASE";
+        yield return "NET";
+        yield return "NETCOREAPP";
+        var major = Environment.Version.Major;
+        yield return $"NET{major}_0";
+        for (var v = 5; v <= major; v++) yield return $"NET{v}_0_OR_GREATER";
+        foreach (var define in req.Defines) yield return define;
+    }
+
     // ----- CLASS -----
 
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
@@ -214,7 +441,8 @@ public class Host
             return;
         }
 
-        var obj = Activator.CreateInstance(t);
+        // Scripts may hide their parameterless constructor to enforce initialization
+        var obj = Activator.CreateInstance(t, nonPublic: true);
         if (obj == null) {
             error = (int)Error.MethodNotFound;
             return;
@@ -223,6 +451,72 @@ public class Host
         result = Pin(obj);
     }
 
+    // Without parameter types the first constructor with a matching parameter count is used
+    static ConstructorInfo? FindConstructor(Type t, IntPtr parameters, int argCount)
+    {
+        var parameterTypes = parameters == IntPtr.Zero ? null : JsonSerializer.Deserialize<string[]>(ReadUtf8Z(parameters));
+
+        var flags = BindingFlags.Public | BindingFlags.NonPublic | BindingFlags.Instance;
+        return t.GetConstructors(flags)
+            .Where(c => parameterTypes == null
+                ? c.GetParameters().Length == argCount
+                : ParametersMatch(c.GetParameters(), parameterTypes))
+            .FirstOrDefault();
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetConstructorInfoDelegate(IntPtr klass, IntPtr parametersUtf8Z, int argCount, out IntPtr result, out int error);
+    public static void GetConstructorInfo(IntPtr klass, IntPtr parameters, int argCount, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+
+        var ctor = FindConstructor(t, parameters, argCount);
+        if (ctor == null) {
+            error = (int)Error.MethodNotFound;
+            return;
+        }
+
+        result = AllocJson(DescribeMethod(ctor));
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void NewWithArgsDelegate(IntPtr klass, IntPtr parametersUtf8Z, int argCount, void** args, out IntPtr result, out int error);
+    public unsafe static void NewWithArgs(IntPtr klass, IntPtr parameters, int argCount, void** argv, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+
+        var ctor = FindConstructor(t, parameters, argCount);
+        if (ctor == null) {
+            error = (int)Error.MethodNotFound;
+            return;
+        }
+        if (ctor.GetParameters().Length != argCount) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+
+        try {
+            result = Pin(ctor.Invoke(ReadArguments(ctor.GetParameters(), argv)));
+        } catch {
+            // The constructor threw or an argument couldn't be read
+            error = (int)Error.InvocationFailed;
+        }
+    }
+
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     public delegate void IsAssignableFromDelegate(IntPtr baseKlass, IntPtr targetKlass, out int result, out int error);
     public static void IsAssignableFrom(IntPtr baseKlass, IntPtr targetKlass, out int result, out int error)
@@ -243,6 +537,55 @@ public class Host
         result = baseType.IsAssignableFrom(targetType) ? 1 : 0;
     }
 
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void HasAttributeDelegate(IntPtr klass, IntPtr attributeKlass, out int result, out int error);
+    public static void HasAttribute(IntPtr klass, IntPtr attributeKlass, out int result, out int error)
+    {
+        result = 0;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var attributeType = Ref<Type>(attributeKlass);
+        if (attributeType == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        result = t.IsDefined(attributeType, inherit: true) ? 1 : 0;
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetTypesDelegate(Assembly assembly, out IntPtr result, out int error);
+    public static void GetTypes(Assembly assembly, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        if (assembly == null) {
+            error = (int)Error.AssemblyNotLoaded;
+            return;
+        }
+
+        // Only classes that can be instantiated, compiler generated types have names that aren't valid C#
+        var names = assembly.GetTypes()
+            .Where(t => t.IsClass && !t.IsAbstract && !t.IsGenericTypeDefinition)
+            .Where(t => !t.IsDefined(typeof(CompilerGeneratedAttribute), false))
+            .Select(t => t.FullName)
+            .Where(n => n != null)
+            .ToArray();
+
+        string response = JsonSerializer.Serialize(names);
+
+        byte[] bytes = System.Text.Encoding.UTF8.GetBytes(response);
+
+        result = Marshal.AllocHGlobal(bytes.Length + 1);
+        Marshal.Copy(bytes, 0, result, bytes.Length);
+        Marshal.WriteByte(result, bytes.Length, 0);
+    }
+
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     public delegate void GetMetaDataDelegate(IntPtr klass, out IntPtr result, out int error);
     public static void GetMetaData(IntPtr klass, out IntPtr result, out int error)
@@ -291,6 +634,166 @@ public class Host
         Marshal.WriteByte(result, bytes.Length, 0);
     }
 
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetTypeInfoDelegate(IntPtr klass, out IntPtr result, out int error);
+    public static void GetTypeInfo(IntPtr klass, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+
+        result = AllocJson(Describe(t));
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void DescribeAssemblyDelegate(Assembly assembly, out IntPtr result, out int error);
+    public static void DescribeAssembly(Assembly assembly, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        if (assembly == null) {
+            error = (int)Error.AssemblyNotLoaded;
+            return;
+        }
+
+        var types = assembly.GetTypes()
+            .Where(t => !t.IsDefined(typeof(CompilerGeneratedAttribute), false))
+            .Select(Describe)
+            .ToArray();
+
+        result = AllocJson(types);
+    }
+
+    static IntPtr AllocJson(object? value, JsonSerializerOptions? options = null)
+    {
+        byte[] bytes = System.Text.Encoding.UTF8.GetBytes(JsonSerializer.Serialize(value, options));
+
+        var result = Marshal.AllocHGlobal(bytes.Length + 1);
+        Marshal.Copy(bytes, 0, result, bytes.Length);
+        Marshal.WriteByte(result, bytes.Length, 0);
+        return result;
+    }
+
+    // Readable C# style name, e.g. `System.Collections.Generic.List<System.Int32>`
+    static string TypeName(Type t)
+    {
+        if (t.IsGenericParameter) return t.Name;
+        if (t.IsByRef) return TypeName(t.GetElementType()!) + "&";
+        if (t.IsPointer) return TypeName(t.GetElementType()!) + "*";
+        if (t.IsArray) return TypeName(t.GetElementType()!) + "[" + new string(',', t.GetArrayRank() - 1) + "]";
+        if (t.IsGenericType) {
+            var definition = t.GetGenericTypeDefinition();
+            var name = definition.FullName ?? definition.Name;
+            var tick = name.IndexOf('`');
+            if (tick >= 0) name = name.Substring(0, tick);
+            return $"{name}<{string.Join(", ", t.GetGenericArguments().Select(TypeName))}>";
+        }
+        return t.FullName ?? t.Name;
+    }
+
+    static AttributeDescription[] DescribeAttributes(IEnumerable<CustomAttributeData> attributes) => attributes
+        .Select(a => new AttributeDescription {
+            Type = TypeName(a.AttributeType),
+            Arguments = a.ConstructorArguments.Select(arg => arg.Value?.ToString()).ToArray(),
+        })
+        .ToArray();
+
+    static MethodDescription DescribeMethod(MethodBase m) => new MethodDescription {
+        Name = m.Name,
+        ReturnType = m is MethodInfo info ? TypeName(info.ReturnType) : "System.Void",
+        IsStatic = m.IsStatic,
+        IsPublic = m.IsPublic,
+        IsAbstract = m.IsAbstract,
+        IsVirtual = m.IsVirtual,
+        GenericParameters = m.IsGenericMethodDefinition ? m.GetGenericArguments().Select(TypeName).ToArray() : Array.Empty<string>(),
+        Parameters = m.GetParameters()
+            .Select(p => new ParameterDescription {
+                Name = p.Name,
+                Type = TypeName(p.ParameterType),
+                IsValueType = p.ParameterType.IsValueType,
+                Size = MarshalledSize(p.ParameterType),
+                ElementSize = ElementSize(p.ParameterType),
+                IsByRefLike = (p.ParameterType.IsByRef ? p.ParameterType.GetElementType()! : p.ParameterType).IsByRefLike,
+                IsOut = p.IsOut,
+                IsOptional = p.IsOptional,
+            })
+            .ToArray(),
+        Attributes = DescribeAttributes(m.CustomAttributes),
+    };
+
+    static int ElementSize(Type t)
+    {
+        var elem = t.IsByRef ? t.GetElementType()! : t;
+        return elem.IsArray ? MarshalledSize(elem.GetElementType()!) : 0;
+    }
+
+    static int MarshalledSize(Type t)
+    {
+        if (!t.IsValueType || t.IsPrimitive || t.IsEnum || Nullable.GetUnderlyingType(t) != null) return 0;
+        try {
+            return Marshal.SizeOf(t);
+        } catch (ArgumentException) {
+            // Not marshallable, e.g. generic structs
+            return 0;
+        }
+    }
+
+    static TypeDescription Describe(Type t)
+    {
+        // Inherited members are described by the base type
+        var flags = BindingFlags.Public | BindingFlags.NonPublic | BindingFlags.Static | BindingFlags.Instance | BindingFlags.DeclaredOnly;
+
+        return new TypeDescription {
+            FullName = TypeName(t),
+            Name = t.Name,
+            Namespace = t.Namespace,
+            BaseType = t.BaseType == null ? null : TypeName(t.BaseType),
+            Interfaces = t.GetInterfaces().Select(TypeName).ToArray(),
+            IsAbstract = t.IsAbstract,
+            IsInterface = t.IsInterface,
+            IsValueType = t.IsValueType,
+            IsEnum = t.IsEnum,
+            GenericParameters = t.IsGenericTypeDefinition ? t.GetGenericArguments().Select(TypeName).ToArray() : Array.Empty<string>(),
+            Fields = t.GetFields(flags)
+                .Where(f => !f.IsDefined(typeof(CompilerGeneratedAttribute), false))
+                .Select(f => new FieldDescription {
+                    Name = f.Name,
+                    Type = TypeName(f.FieldType),
+                    IsStatic = f.IsStatic,
+                    IsPublic = f.IsPublic,
+                    IsReadOnly = f.IsInitOnly || f.IsLiteral,
+                    Attributes = DescribeAttributes(f.CustomAttributes),
+                })
+                .ToArray(),
+            Properties = t.GetProperties(flags)
+                .Select(p => new PropertyDescription {
+                    Name = p.Name,
+                    Type = TypeName(p.PropertyType),
+                    IsStatic = (p.GetGetMethod(true)?.IsStatic ?? false) || (p.GetSetMethod(true)?.IsStatic ?? false),
+                    CanRead = p.CanRead,
+                    CanWrite = p.CanWrite,
+                    Attributes = DescribeAttributes(p.CustomAttributes),
+                })
+                .ToArray(),
+            Constructors = t.GetConstructors(flags)
+                .Where(c => !c.IsStatic)
+                .Select(DescribeMethod)
+                .ToArray(),
+            // Property accessors and operators are special names
+            Methods = t.GetMethods(flags)
+                .Where(m => !m.IsSpecialName)
+                .Select(DescribeMethod)
+                .ToArray(),
+            Attributes = DescribeAttributes(t.CustomAttributes),
+        };
+    }
+
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     public delegate void GetMethodDelegate(IntPtr klass, IntPtr nameUtf8Z, int argCount, out IntPtr result, out int error);
     public static void GetMethod(IntPtr klass, IntPtr name, int argCount, out IntPtr result, out int error)
@@ -308,7 +811,58 @@ public class Host
         var flags = BindingFlags.Public | BindingFlags.NonPublic | BindingFlags.Static | BindingFlags.Instance;
         var cand = t.GetMethods(flags).Where(m => m.Name == methodName && m.GetParameters().Length == argCount).FirstOrDefault<MethodInfo>();
 
-        result = cand == null ? IntPtr.Zero : Pin(cand);
+        result = cand == null ? IntPtr.Zero : Pin(CompileInvoker(cand));
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetMethodWithSignatureDelegate(IntPtr klass, IntPtr nameUtf8Z, IntPtr parametersUtf8Z, out IntPtr result, out int error);
+    public static void GetMethodWithSignature(IntPtr klass, IntPtr name, IntPtr parameters, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var methodName = ReadUtf8Z(name);
+        var parameterTypes = JsonSerializer.Deserialize<string[]>(ReadUtf8Z(parameters)) ?? Array.Empty<string>();
+
+        var flags = BindingFlags.Public | BindingFlags.NonPublic | BindingFlags.Static | BindingFlags.Instance;
+        var cand = t.GetMethods(flags)
+            .Where(m => m.Name == methodName && ParametersMatch(m.GetParameters(), parameterTypes))
+            .FirstOrDefault<MethodInfo>();
+
+        result = cand == null ? IntPtr.Zero : Pin(CompileInvoker(cand));
+    }
+
+    // Parameter types can be named by their full name, e.g. `System.Single`, or their short name, e.g. `Single`
+    static bool ParametersMatch(ParameterInfo[] parameters, string[] types)
+    {
+        if (parameters.Length != types.Length) return false;
+        for (var i = 0; i < parameters.Length; i++)
+        {
+            var t = parameters[i].ParameterType;
+            if (TypeName(t) != types[i] && t.Name != types[i]) return false;
+        }
+        return true;
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetMethodInfoDelegate(IntPtr method, out IntPtr result, out int error);
+    public static void GetMethodInfo(IntPtr method, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var m = Ref<Invoker>(method);
+        if (m == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+
+        result = AllocJson(DescribeMethod(m.Method));
     }
 
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
@@ -450,46 +1004,693 @@ public class Host
         Marshal.WriteByte(result, bytes.Length, 0);
     }
 
-    // ----- METHOD -----
-
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
-    public unsafe delegate void RuntimeInvokeDelegate(IntPtr method, void* instance, void** args, out int error);
-    public unsafe static void RuntimeInvoke(IntPtr method, void* instancePtr, void** argv, out int error)
+    public delegate void GetFieldInfoDelegate(IntPtr instance, IntPtr name, out IntPtr result, out int error);
+    public static void GetFieldInfo(IntPtr instance, IntPtr name, out IntPtr result, out int error)
     {
+        result = IntPtr.Zero;
         error = 0;
 
-        var m = Ref<MethodInfo>(method);
-        if (m == null) {
+        var target = Ref<object>(instance);
+        if (target == null) {
             error = (int)Error.MissingRequiredArgument;
             return;
         }
-        var parameters = m.GetParameters();
-        var argc = parameters.Length;
+        var fieldName = ReadUtf8Z(name);
+        var flags = BindingFlags.Instance | BindingFlags.Public;
 
-        object? instance = null;
-        if (instancePtr != null)
-        {
-            instance = Ref<object>((IntPtr)instancePtr);
+        var fi = target.GetType().GetField(fieldName, flags);
+        if (fi == null) {
+            error = (int)Error.FieldNotFound;
+            return;
         }
+        result = AllocJson(DescribeValue(fi.Name, fi.FieldType));
+    }
 
-        var args = new object?[argc];
-        for (var i = 0; i < argc; i++)
-        {
-            if (parameters[i].ParameterType.IsValueType) {
-                args[i] = ReadValueAsObject(argv[i], parameters[i].ParameterType);
-            } else if (parameters[i].ParameterType == typeof(string)) {
-                args[i] = Marshal.PtrToStringUTF8((IntPtr)argv[i]);
-            } else {
-                args[i] = GCHandle.FromIntPtr((IntPtr)argv[i]).Target;
-            }
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetPropertyInfoDelegate(IntPtr instance, IntPtr name, out IntPtr result, out int error);
+    public static void GetPropertyInfo(IntPtr instance, IntPtr name, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var target = Ref<object>(instance);
+        if (target == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
         }
+        var propertyName = ReadUtf8Z(name);
+        var flags = BindingFlags.Instance | BindingFlags.Public;
 
-        m.Invoke(instance, args);
+        var pi = target.GetType().GetProperty(propertyName, flags);
+        if (pi == null) {
+            error = (int)Error.PropertyNotFound;
+            return;
+        }
+        result = AllocJson(DescribeValue(pi.Name, pi.PropertyType));
     }
 
-    private static unsafe object? ReadValueAsObject(void* p, Type t)
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void GetFieldValueIntoDelegate(IntPtr instance, IntPtr name, void* buffer, int size, out int error);
+    public unsafe static void GetFieldValueInto(IntPtr instance, IntPtr name, void* buffer, int size, out int error)
     {
-        var elem = t.IsByRef ? t.GetElementType() : t;
+        error = 0;
+
+        var target = Ref<object>(instance);
+        if (target == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var fieldName = ReadUtf8Z(name);
+        var flags = BindingFlags.Instance | BindingFlags.Public;
+
+        var fi = target.GetType().GetField(fieldName, flags);
+        if (fi == null) {
+            error = (int)Error.FieldNotFound;
+            return;
+        }
+        if (BlittableSize(fi.FieldType) < 0) {
+            error = (int)Error.NotBlittable;
+            return;
+        }
+
+        error = WriteBlittable(fi.FieldType, fi.GetValue(target), buffer, size);
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void GetPropertyValueIntoDelegate(IntPtr instance, IntPtr name, void* buffer, int size, out int error);
+    public unsafe static void GetPropertyValueInto(IntPtr instance, IntPtr name, void* buffer, int size, out int error)
+    {
+        error = 0;
+
+        var target = Ref<object>(instance);
+        if (target == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var propertyName = ReadUtf8Z(name);
+        var flags = BindingFlags.Instance | BindingFlags.Public;
+
+        var pi = target.GetType().GetProperty(propertyName, flags);
+        if (pi == null) {
+            error = (int)Error.PropertyNotFound;
+            return;
+        }
+
+        if (!pi.CanRead) {
+            error = (int)Error.MissingGetter;
+            return;
+        }
+        if (BlittableSize(pi.PropertyType) < 0) {
+            error = (int)Error.NotBlittable;
+            return;
+        }
+
+        error = WriteBlittable(pi.PropertyType, pi.GetValue(target), buffer, size);
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetFieldAccessorDelegate(IntPtr klass, IntPtr nameUtf8Z, out IntPtr result, out int error);
+    public static void GetFieldAccessor(IntPtr klass, IntPtr name, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var fieldName = ReadUtf8Z(name);
+        var flags = BindingFlags.Instance | BindingFlags.Public;
+
+        var fi = t.GetField(fieldName, flags);
+        if (fi == null) {
+            error = (int)Error.FieldNotFound;
+            return;
+        }
+
+        var writable = (fi.Attributes & (FieldAttributes.InitOnly | FieldAttributes.Literal)) == 0;
+        result = Pin(CompileAccessor(fi, fi.FieldType, true, writable, Error.ReadonlyField));
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetPropertyAccessorDelegate(IntPtr klass, IntPtr nameUtf8Z, out IntPtr result, out int error);
+    public static void GetPropertyAccessor(IntPtr klass, IntPtr name, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var propertyName = ReadUtf8Z(name);
+        var flags = BindingFlags.Instance | BindingFlags.Public;
+
+        var pi = t.GetProperty(propertyName, flags);
+        if (pi == null || pi.GetIndexParameters().Length > 0) {
+            error = (int)Error.PropertyNotFound;
+            return;
+        }
+
+        result = Pin(CompileAccessor(pi, pi.PropertyType, pi.CanRead, pi.CanWrite, Error.MissingSetter));
+    }
+
+    static Accessor CompileAccessor(MemberInfo member, Type valueType, bool canRead, bool canWrite, Error setError)
+    {
+        var accessor = new Accessor {
+            Member = member,
+            ValueType = valueType,
+            Size = BlittableSize(valueType),
+            SetError = setError,
+        };
+
+        var instance = Expression.Parameter(typeof(object), "instance");
+        var buffer = Expression.Parameter(typeof(IntPtr), "buffer");
+        var value = Expression.Parameter(typeof(object), "value");
+        var access = Expression.MakeMemberAccess(Expression.Convert(instance, member.DeclaringType!), member);
+
+        if (canRead) {
+            accessor.Get = Expression.Lambda<Func<object, object?>>(
+                Expression.Convert(access, typeof(object)), instance).Compile();
+            if (accessor.Size >= 0) {
+                var write = typeof(Host).GetMethod(nameof(WriteTo), BindingFlags.Static | BindingFlags.NonPublic)!;
+                accessor.GetInto = Expression.Lambda<Action<object, IntPtr>>(
+                    Expression.Call(write.MakeGenericMethod(valueType), buffer, access), instance, buffer).Compile();
+            }
+        }
+
+        // Members of structs would be assigned on an unboxed copy
+        if (canWrite && member.DeclaringType!.IsValueType) {
+            if (member is FieldInfo fi) {
+                accessor.Set = (target, v) => fi.SetValue(target, v);
+            } else {
+                var pi = (PropertyInfo)member;
+                accessor.Set = (target, v) => pi.SetValue(target, v);
+            }
+        } else if (canWrite) {
+            // `null` from the host sets value types to their default, the same as reflection does
+            var converted = Expression.Condition(
+                Expression.ReferenceEqual(value, Expression.Constant(null)),
+                Expression.Default(valueType),
+                Expression.Convert(value, valueType));
+            accessor.Set = Expression.Lambda<Action<object, object?>>(
+                Expression.Assign(access, converted), instance, value).Compile();
+            if (accessor.Size >= 0) {
+                var read = typeof(Host).GetMethod(nameof(ReadFrom), BindingFlags.Static | BindingFlags.NonPublic)!;
+                accessor.SetFrom = Expression.Lambda<Action<object, IntPtr>>(
+                    Expression.Assign(access, Expression.Call(read.MakeGenericMethod(valueType), buffer)), instance, buffer).Compile();
+            }
+        }
+
+        return accessor;
+    }
+
+    static unsafe void WriteTo<T>(IntPtr buffer, T value) => Unsafe.Write((void*)buffer, value);
+    static unsafe T ReadFrom<T>(IntPtr buffer) => Unsafe.Read<T>((void*)buffer);
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetAccessorInfoDelegate(IntPtr accessor, out IntPtr result, out int error);
+    public static void GetAccessorInfo(IntPtr accessor, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var a = Ref<Accessor>(accessor);
+        if (a == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+
+        result = AllocJson(DescribeValue(a.Member.Name, a.ValueType));
+    }
+
+    // Type of a field or property described like a parameter, so the host can check values before writing them
+    static ParameterDescription DescribeValue(string name, Type t) => new ParameterDescription {
+        Name = name,
+        Type = TypeName(t),
+        IsValueType = t.IsValueType,
+        Size = MarshalledSize(t),
+        ElementSize = ElementSize(t),
+        IsByRefLike = t.IsByRefLike,
+    };
+
+    // Resolve the accessor and the instance it is used on, checking the instance has the member
+    static bool AccessorTarget(IntPtr accessor, IntPtr instance, out Accessor a, out object target, out int error)
+    {
+        a = Ref<Accessor>(accessor)!;
+        target = Ref<object>(instance)!;
+        error = 0;
+        if (a == null || target == null) {
+            error = (int)Error.MissingRequiredArgument;
+        } else if (!a.Member.DeclaringType!.IsInstanceOfType(target)) {
+            error = (int)Error.InvalidInstance;
+        }
+        return error == 0;
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void AccessorGetDelegate(IntPtr accessor, IntPtr instance, out IntPtr result, out int error);
+    public static void AccessorGet(IntPtr accessor, IntPtr instance, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        if (!AccessorTarget(accessor, instance, out var a, out var target, out error)) return;
+
+        if (a.Get == null) {
+            error = (int)Error.MissingGetter;
+            return;
+        }
+
+        result = AllocJson(a.Get(target), new JsonSerializerOptions { IncludeFields = true });
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void AccessorGetIntoDelegate(IntPtr accessor, IntPtr instance, void* buffer, int size, out int error);
+    public unsafe static void AccessorGetInto(IntPtr accessor, IntPtr instance, void* buffer, int size, out int error)
+    {
+        if (!AccessorTarget(accessor, instance, out var a, out var target, out error)) return;
+
+        if (a.Get == null) {
+            error = (int)Error.MissingGetter;
+            return;
+        }
+        if (a.GetInto == null) {
+            error = (int)Error.NotBlittable;
+            return;
+        }
+        if (a.Size != size) {
+            error = (int)Error.SizeMismatch;
+            return;
+        }
+
+        a.GetInto(target, (IntPtr)buffer);
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void AccessorSetDelegate(IntPtr accessor, IntPtr instance, void* value, out int error);
+    public unsafe static void AccessorSet(IntPtr accessor, IntPtr instance, void* value, out int error)
+    {
+        if (!AccessorTarget(accessor, instance, out var a, out var target, out error)) return;
+
+        if (a.Set == null) {
+            error = (int)a.SetError;
+            return;
+        }
+
+        if (a.SetFrom != null && value != null) {
+            a.SetFrom(target, (IntPtr)value);
+        } else {
+            a.Set(target, ReadValueAsObject(value, a.ValueType));
+        }
+    }
+
+    static readonly ConcurrentDictionary<Type, int> BlittableSizes = new();
+
+    // Size of value types whose managed layout can be copied as is, -1 when they have to be serialized
+    static int BlittableSize(Type t) => BlittableSizes.GetOrAdd(t, static t => {
+        if (!t.IsValueType || t.IsByRefLike || t.ContainsGenericParameters || Nullable.GetUnderlyingType(t) != null) return -1;
+        try {
+            // Pinning fails for structs with references, `bool` or `char`
+            GCHandle.Alloc(Activator.CreateInstance(t), GCHandleType.Pinned).Free();
+            return Marshal.SizeOf(t.IsEnum ? Enum.GetUnderlyingType(t) : t);
+        } catch (ArgumentException) {
+            return -1;
+        }
+    });
+
+    // Copy a boxed blittable value into the buffer provided by the host
+    static unsafe int WriteBlittable(Type t, object? value, void* buffer, int size)
+    {
+        if (value == null) return (int)Error.NotBlittable;
+        if (BlittableSize(t) != size) return (int)Error.SizeMismatch;
+
+        var pinned = GCHandle.Alloc(value, GCHandleType.Pinned);
+        try {
+            Buffer.MemoryCopy((void*)pinned.AddrOfPinnedObject(), buffer, size, size);
+        } finally {
+            pinned.Free();
+        }
+        return 0;
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void SetStaticFieldValueDelegate(IntPtr klass, IntPtr name, void* value, out int error);
+    public unsafe static void SetStaticFieldValue(IntPtr klass, IntPtr name, void* value, out int error)
+    {
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var fieldName = ReadUtf8Z(name);
+        var flags = BindingFlags.Static | BindingFlags.Public;
+
+        var fi = t.GetField(fieldName, flags);
+        if (fi == null) {
+            error = (int)Error.FieldNotFound;
+            return;
+        }
+
+        if ((fi.Attributes & (FieldAttributes.InitOnly | FieldAttributes.Literal)) != 0) {
+            error = (int)Error.ReadonlyField;
+            return;
+        }
+
+        fi.SetValue(null, ReadValueAsObject(value, fi.FieldType));
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void GetStaticFieldValueDelegate(IntPtr klass, IntPtr name, out IntPtr result, out int error);
+    public unsafe static void GetStaticFieldValue(IntPtr klass, IntPtr name, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var fieldName = ReadUtf8Z(name);
+        var flags = BindingFlags.Static | BindingFlags.Public;
+
+        var fi = t.GetField(fieldName, flags);
+        if (fi == null) {
+            error = (int)Error.FieldNotFound;
+            return;
+        }
+        result = AllocJson(fi.GetValue(null), new JsonSerializerOptions { IncludeFields = true });
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void SetStaticPropertyValueDelegate(IntPtr klass, IntPtr name, void* value, out int error);
+    public unsafe static void SetStaticPropertyValue(IntPtr klass, IntPtr name, void* value, out int error)
+    {
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var propertyName = ReadUtf8Z(name);
+        var flags = BindingFlags.Static | BindingFlags.Public;
+
+        var pi = t.GetProperty(propertyName, flags);
+        if (pi == null) {
+            error = (int)Error.PropertyNotFound;
+            return;
+        }
+
+        if (!pi.CanWrite) {
+            error = (int)Error.MissingSetter;
+            return;
+        }
+
+        pi.SetValue(null, ReadValueAsObject(value, pi.PropertyType));
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void GetStaticPropertyValueDelegate(IntPtr klass, IntPtr name, out IntPtr result, out int error);
+    public unsafe static void GetStaticPropertyValue(IntPtr klass, IntPtr name, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var propertyName = ReadUtf8Z(name);
+        var flags = BindingFlags.Static | BindingFlags.Public;
+
+        var pi = t.GetProperty(propertyName, flags);
+        if (pi == null) {
+            error = (int)Error.PropertyNotFound;
+            return;
+        }
+
+        if (!pi.CanRead) {
+            error = (int)Error.MissingGetter;
+            return;
+        }
+
+        result = AllocJson(pi.GetValue(null), new JsonSerializerOptions { IncludeFields = true });
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetStaticFieldInfoDelegate(IntPtr klass, IntPtr name, out IntPtr result, out int error);
+    public static void GetStaticFieldInfo(IntPtr klass, IntPtr name, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var fieldName = ReadUtf8Z(name);
+        var flags = BindingFlags.Static | BindingFlags.Public;
+
+        var fi = t.GetField(fieldName, flags);
+        if (fi == null) {
+            error = (int)Error.FieldNotFound;
+            return;
+        }
+        result = AllocJson(DescribeValue(fi.Name, fi.FieldType));
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void GetStaticPropertyInfoDelegate(IntPtr klass, IntPtr name, out IntPtr result, out int error);
+    public static void GetStaticPropertyInfo(IntPtr klass, IntPtr name, out IntPtr result, out int error)
+    {
+        result = IntPtr.Zero;
+        error = 0;
+
+        var t = Ref<Type>(klass);
+        if (t == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+        var propertyName = ReadUtf8Z(name);
+        var flags = BindingFlags.Static | BindingFlags.Public;
+
+        var pi = t.GetProperty(propertyName, flags);
+        if (pi == null) {
+            error = (int)Error.PropertyNotFound;
+            return;
+        }
+        result = AllocJson(DescribeValue(pi.Name, pi.PropertyType));
+    }
+
+    // ----- METHOD -----
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void RuntimeInvokeDelegate(IntPtr method, void* instance, void** args, out int error);
+    public unsafe static void RuntimeInvoke(IntPtr method, void* instancePtr, void** argv, out int error)
+    {
+        error = 0;
+
+        var m = Ref<Invoker>(method);
+        if (m == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+
+        object? instance = null;
+        if (instancePtr != null)
+        {
+            instance = Ref<object>((IntPtr)instancePtr);
+        }
+
+        try {
+            if (m.Thunk != null) {
+                m.Thunk(instance, (IntPtr)argv);
+            } else {
+                m.Method.Invoke(instance, ReadArguments(m.Method.GetParameters(), argv));
+            }
+        } catch {
+            error = (int)Error.InvocationFailed;
+        }
+    }
+
+    // Always invokes with `MethodInfo.Invoke`, kept to compare against the compiled thunks
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void RuntimeInvokeReflectionDelegate(IntPtr method, void* instance, void** args, out int error);
+    public unsafe static void RuntimeInvokeReflection(IntPtr method, void* instancePtr, void** argv, out int error)
+    {
+        error = 0;
+
+        var m = Ref<Invoker>(method);
+        if (m == null) {
+            error = (int)Error.MissingRequiredArgument;
+            return;
+        }
+
+        object? instance = null;
+        if (instancePtr != null)
+        {
+            instance = Ref<object>((IntPtr)instancePtr);
+        }
+
+        try {
+            m.Method.Invoke(instance, ReadArguments(m.Method.GetParameters(), argv));
+        } catch {
+            error = (int)Error.InvocationFailed;
+        }
+    }
+
+    // Invokes `methods[i]` on `instances[i]` with the same arguments, writing why each call failed to `errors[i]`
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public unsafe delegate void RuntimeInvokeBatchDelegate(int count, IntPtr* methods, IntPtr* instances, void** args, int* errors, out int error);
+    public unsafe static void RuntimeInvokeBatch(int count, IntPtr* methods, IntPtr* instances, void** argv, int* errors, out int error)
+    {
+        error = 0;
+
+        // Methods without a thunk convert the shared arguments once and reuse them for the next calls
+        Invoker? converted = null;
+        object?[]? arguments = null;
+
+        for (var i = 0; i < count; i++)
+        {
+            errors[i] = 0;
+            if (methods[i] == IntPtr.Zero || instances[i] == IntPtr.Zero) {
+                errors[i] = (int)Error.MissingRequiredArgument;
+                continue;
+            }
+
+            var m = Ref<Invoker>(methods[i]);
+            var instance = Ref<object>(instances[i]);
+            if (m == null || instance == null) {
+                errors[i] = (int)Error.MissingRequiredArgument;
+                continue;
+            }
+            if (!m.Method.IsStatic && !m.Method.DeclaringType!.IsInstanceOfType(instance)) {
+                errors[i] = (int)Error.InvalidInstance;
+                continue;
+            }
+
+            // One script throwing doesn't stop the others from running
+            try {
+                if (m.Thunk != null) {
+                    m.Thunk(instance, (IntPtr)argv);
+                } else {
+                    if (converted != m) {
+                        arguments = ReadArguments(m.Method.GetParameters(), argv);
+                        converted = m;
+                    }
+                    m.Method.Invoke(instance, arguments);
+                }
+            } catch {
+                errors[i] = (int)Error.InvocationFailed;
+            }
+        }
+    }
+
+    static Invoker CompileInvoker(MethodInfo method)
+    {
+        var invoker = new Invoker { Method = method };
+
+        var parameters = method.GetParameters();
+        var compilable = !method.ContainsGenericParameters
+            && !(method.DeclaringType?.IsValueType ?? true)
+            && parameters.All(p => !p.ParameterType.IsByRef && !p.ParameterType.IsPointer && !p.ParameterType.IsByRefLike);
+        if (!compilable) return invoker;
+
+        var instance = Expression.Parameter(typeof(object), "instance");
+        var argv = Expression.Parameter(typeof(IntPtr), "argv");
+
+        // Blittable values are read in place, everything else is converted the same way reflection receives it
+        var arguments = parameters.Select((p, i) => {
+            var t = p.ParameterType;
+            var index = Expression.Constant(i);
+            if (t == typeof(bool)) {
+                return (Expression)Expression.Call(typeof(Host).GetMethod(nameof(ReadBool), BindingFlags.Static | BindingFlags.NonPublic)!, argv, index);
+            }
+            var read = BlittableSize(t) >= 0 ? nameof(ReadBlittable) : nameof(ReadConverted);
+            return Expression.Call(typeof(Host).GetMethod(read, BindingFlags.Static | BindingFlags.NonPublic)!.MakeGenericMethod(t), argv, index);
+        });
+
+        var call = method.IsStatic
+            ? Expression.Call(method, arguments)
+            : Expression.Call(Expression.Convert(instance, method.DeclaringType!), method, arguments);
+        invoker.Thunk = Expression.Lambda<Action<object?, IntPtr>>(call, instance, argv).Compile();
+        return invoker;
+    }
+
+    // `None` from the host is passed as a null pointer, value types receive their default
+    static unsafe T ReadBlittable<T>(IntPtr argv, int index)
+    {
+        var p = ((void**)argv)[index];
+        return p == null ? default! : Unsafe.Read<T>(p);
+    }
+
+    // Rust bools are a single byte
+    static unsafe bool ReadBool(IntPtr argv, int index)
+    {
+        var p = ((void**)argv)[index];
+        return p != null && *(byte*)p != 0;
+    }
+
+    static unsafe T ReadConverted<T>(IntPtr argv, int index)
+    {
+        var value = ReadValueAsObject(((void**)argv)[index], typeof(T));
+        return value == null ? default! : (T)value;
+    }
+
+    private static unsafe object?[] ReadArguments(ParameterInfo[] parameters, void** argv)
+    {
+        var args = new object?[parameters.Length];
+        for (var i = 0; i < parameters.Length; i++)
+        {
+            args[i] = ReadValueAsObject(argv[i], parameters[i].ParameterType);
+        }
+        return args;
+    }
+
+    private static unsafe object? ReadValueAsObject(void* p, Type t)
+    {
+        var elem = t.IsByRef ? t.GetElementType()! : t;
+
+        // `None` from the host, value types receive their default
+        if (p == null) return null;
+
+        var underlying = Nullable.GetUnderlyingType(elem);
+        if (underlying != null) return ReadValueAsObject(p, underlying);
+
+        if (elem == typeof(string)) return Marshal.PtrToStringUTF8((IntPtr)p);
+
+        // Arrays of value types are passed as a { data, length } header and copied
+        if (elem.IsArray && elem.GetElementType()!.IsValueType)
+        {
+            var elementType = elem.GetElementType()!;
+            // `bool` and `char` can't be pinned inside structs but are copied as primitives
+            var elementSize = elementType.IsPrimitive ? -1 : BlittableSize(elementType);
+            if (!elementType.IsPrimitive && elementSize < 0) {
+                throw new ArgumentException($"{TypeName(elementType)} is not blittable and can't be copied into an array");
+            }
+
+            var data = Unsafe.Read<IntPtr>(p);
+            var length = Unsafe.Read<int>((byte*)p + IntPtr.Size);
+            var array = Array.CreateInstance(elementType, length);
+            var size = elementType.IsPrimitive ? Buffer.ByteLength(array) : (long)length * elementSize;
+            var pinned = GCHandle.Alloc(array, GCHandleType.Pinned);
+            try {
+                Buffer.MemoryCopy((void*)data, (void*)pinned.AddrOfPinnedObject(), size, size);
+            } finally {
+                pinned.Free();
+            }
+            return array;
+        }
+
+        // Other reference types are passed as object handles
+        if (!elem.IsValueType) return GCHandle.FromIntPtr((IntPtr)p).Target;
 
         if (elem == typeof(IntPtr) || elem == typeof(nint))
         {
@@ -506,7 +1707,7 @@ public class Host
         if (elem == typeof(ushort)) return Unsafe.Read<ushort>(p);
         if (elem == typeof(byte))   return Unsafe.Read<byte>(p);
         if (elem == typeof(sbyte))  return Unsafe.Read<sbyte>(p);
-        if (elem == typeof(bool))   return Unsafe.Read<byte>(p) != 0;       // define size!
+        if (elem == typeof(bool))   return Unsafe.Read<byte>(p) != 0;       // rust bools are a single byte
         if (elem == typeof(float))  return Unsafe.Read<float>(p);
         if (elem == typeof(double)) return Unsafe.Read<double>(p);
         if (elem != null && elem.IsEnum)
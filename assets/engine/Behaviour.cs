@@ -0,0 +1,15 @@
+using System;
+namespace Engine;
+
+/**
+ * <summary>Base class of scripts, classes in the Scripts assembly deriving from it are registered automatically</summary>
+ */
+public abstract class Behaviour {
+}
+
+/**
+ * <summary>Registers a class that doesn't derive from <see cref="Behaviour"/> as a script</summary>
+ */
+[AttributeUsage(AttributeTargets.Class, Inherited = true)]
+public sealed class ScriptAttribute : Attribute {
+}
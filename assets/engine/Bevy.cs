@@ -1,11 +1,8 @@
 using System;
 namespace Engine;
 
-public struct Vector3 {
-    public float x;
-    public float y;
-    public float z;
-
+// Fields are generated from the rust `Vector3` in Generated/Engine.Vector3.g.cs
+public partial struct Vector3 {
     public static Vector3 Zero = new Vector3(0, 0, 0);
 
     public Vector3(float x, float y, float z)
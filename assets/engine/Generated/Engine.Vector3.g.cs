@@ -0,0 +1,13 @@
+// <auto-generated>
+// Generated from `Vector3` by #[derive(ManagedStruct)], changes are overwritten
+// </auto-generated>
+using System.Runtime.InteropServices;
+namespace Engine;
+
+[StructLayout(LayoutKind.Sequential)]
+public partial struct Vector3
+{
+    public System.Single x;
+    public System.Single y;
+    public System.Single z;
+}
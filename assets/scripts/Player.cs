@@ -1,7 +1,7 @@
 using System;
 using Engine;
 
-public class Player {
+public class Player : Behaviour {
     public Vector3 Position { get; set; }
 
     void Awake() {
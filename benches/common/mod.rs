@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy_cs_managed::{CSharpPlugin, ManagedStruct, Runtime, ScriptingDisabled};
+
+/// Matches `Engine.Vector3`
+#[allow(dead_code)]
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable, ManagedStruct)]
+pub struct Vector3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// Start the runtime with the benchmark scripts in `benches/scripts`, `None` when .NET isn't available
+pub fn runtime() -> Option<Runtime> {
+    let config = match workspace() {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("skipping benchmarks, failed to copy the scripts: {error}");
+            return None;
+        }
+    };
+
+    let mut app = App::new();
+    app.add_plugins(CSharpPlugin::default().config(config).optional(true));
+    if let Some(disabled) = app.world().get_resource::<ScriptingDisabled>() {
+        eprintln!("skipping benchmarks, the C# runtime failed to start: {}", disabled.error);
+        return None;
+    }
+    app.world_mut().remove_resource::<Runtime>()
+}
+
+/// Copy the benchmark scripts and engine sources into a directory under `target` so the generated
+/// projects and build output stay out of the source tree, returning its managed config
+fn workspace() -> std::io::Result<PathBuf> {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("benches");
+    copy_sources(Path::new("assets/engine"), &dir.join("engine"))?;
+    copy_sources(Path::new("benches/scripts"), &dir.join("scripts"))?;
+
+    let config = dir.join("managed.config.json");
+    let contents = serde_json::json!({
+        "solution": dir,
+        "engine": { "roots": [dir.join("engine")] },
+        "scripts": { "roots": [dir.join("scripts")] },
+    });
+    std::fs::write(&config, contents.to_string())?;
+    Ok(config)
+}
+
+/// Copy the C# sources in `from` into `to`, keeping the build output of earlier runs
+fn copy_sources(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let path = entry?.path();
+        let target = to.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_sources(&path, &target)?;
+        } else if path.extension().is_some_and(|ext| ext == "cs") {
+            std::fs::copy(&path, target)?;
+        }
+    }
+    Ok(())
+}
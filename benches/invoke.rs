@@ -0,0 +1,40 @@
+//! Compares invoking script methods through the compiled thunks against `MethodInfo.Invoke`
+//!
+//! Runs against the local .NET install: `cargo bench --bench invoke`
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+mod common;
+use common::Vector3;
+
+fn invoke(c: &mut Criterion) {
+    let Some(runtime) = common::runtime() else { return };
+    let script = runtime.create("Bench").unwrap();
+
+    let dt = 0.016f32;
+    let position = Vector3 { x: 1.0, y: 2.0, z: 3.0 };
+
+    let empty = runtime.get_method(&script, "Empty", 0).unwrap().unwrap();
+    let one = runtime.get_method(&script, "One", 1).unwrap().unwrap();
+    let two = runtime.get_method(&script, "Two", 2).unwrap().unwrap();
+
+    let mut group = c.benchmark_group("invoke");
+    group.bench_function("thunk/0", |b| b.iter(|| empty.invoke(()).unwrap()));
+    group.bench_function("reflection/0", |b| b.iter(|| empty.invoke_with_reflection(()).unwrap()));
+    group.bench_function("thunk/1", |b| b.iter(|| one.invoke(black_box(&dt)).unwrap()));
+    group.bench_function("reflection/1", |b| {
+        b.iter(|| one.invoke_with_reflection(black_box(&dt)).unwrap())
+    });
+    group.bench_function("thunk/2", |b| {
+        b.iter(|| two.invoke((black_box(&dt), black_box(&position))).unwrap())
+    });
+    group.bench_function("reflection/2", |b| {
+        b.iter(|| two.invoke_with_reflection((black_box(&dt), black_box(&position))).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, invoke);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+//! Creating scripts, resolving methods and reading metadata
+//!
+//! Runs against the local .NET install: `cargo bench --bench runtime`
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+mod common;
+
+fn runtime(c: &mut Criterion) {
+    // The runtime can only be hosted once per process so every benchmark shares it
+    let Some(mut runtime) = common::runtime() else { return };
+    let script = runtime.create("Bench").unwrap();
+
+    c.bench_function("create", |b| b.iter(|| runtime.create(black_box("Bench")).unwrap()));
+
+    let mut group = c.benchmark_group("get_method");
+    // Resolved methods are cached by their class
+    group.bench_function("hit", |b| {
+        b.iter(|| runtime.get_method(&script, black_box("One"), 1).unwrap().unwrap())
+    });
+    // Methods that aren't found aren't cached so every lookup reaches the runtime
+    group.bench_function("miss", |b| {
+        b.iter(|| runtime.get_method(&script, black_box("Missing"), 1).unwrap())
+    });
+    group.finish();
+
+    c.bench_function("get_meta_data", |b| {
+        b.iter(|| runtime.get_meta_data(black_box(&script)).fields.len())
+    });
+}
+
+criterion_group!(benches, runtime);
+criterion_main!(benches);
@@ -0,0 +1,11 @@
+using Engine;
+
+// Empty bodies so the benchmarks measure the interop and not the script
+public class Bench : Behaviour {
+    public Vector3 Position;
+    public Vector3 Velocity { get; set; }
+
+    public void Empty() {}
+    public void One(float dt) {}
+    public void Two(float dt, Vector3 position) {}
+}
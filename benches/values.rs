@@ -0,0 +1,50 @@
+//! Reading and writing fields and properties as JSON, compared to the blittable copies and cached accessors
+//!
+//! Runs against the local .NET install: `cargo bench --bench values`
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+mod common;
+use common::Vector3;
+
+fn values(c: &mut Criterion) {
+    let Some(runtime) = common::runtime() else { return };
+    let script = runtime.create("Bench").unwrap();
+    let value = Vector3 { x: 1.0, y: 2.0, z: 3.0 };
+    let mut out = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+
+    let mut group = c.benchmark_group("field");
+    group.bench_function("get", |b| {
+        b.iter(|| script.get_field_value::<Vector3>(black_box("Position")).unwrap())
+    });
+    group.bench_function("set", |b| {
+        b.iter(|| script.set_field_value(black_box("Position"), black_box(&value)).unwrap())
+    });
+    group.bench_function("get_into", |b| {
+        b.iter(|| script.get_field_value_into(black_box("Position"), &mut out).unwrap())
+    });
+    let accessor = runtime.field_accessor("Bench", "Position").unwrap();
+    group.bench_function("accessor/get_into", |b| b.iter(|| accessor.get_into(&script, &mut out).unwrap()));
+    group.bench_function("accessor/set", |b| b.iter(|| accessor.set(&script, black_box(&value)).unwrap()));
+    group.finish();
+
+    let mut group = c.benchmark_group("property");
+    group.bench_function("get", |b| {
+        b.iter(|| script.get_property_value::<Vector3>(black_box("Velocity")).unwrap())
+    });
+    group.bench_function("set", |b| {
+        b.iter(|| script.set_property_value(black_box("Velocity"), black_box(&value)).unwrap())
+    });
+    group.bench_function("get_into", |b| {
+        b.iter(|| script.get_property_value_into(black_box("Velocity"), &mut out).unwrap())
+    });
+    let accessor = runtime.property_accessor("Bench", "Velocity").unwrap();
+    group.bench_function("accessor/get_into", |b| b.iter(|| accessor.get_into(&script, &mut out).unwrap()));
+    group.bench_function("accessor/set", |b| b.iter(|| accessor.set(&script, black_box(&value)).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, values);
+criterion_main!(benches);
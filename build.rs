@@ -4,12 +4,19 @@ use log::{Level, Metadata, Record};
 
 #[path = "src/config.rs"]
 mod config;
-use config::{Config, Version};
+use config::{Config, RollForward};
 
+#[allow(dead_code)]
 #[path = "src/dotnet/mod.rs"]
 mod dotnet;
 
+#[allow(dead_code)]
+#[path = "src/project.rs"]
+mod project;
+
 static RUNTIME_CS: &[u8] = include_bytes!("Runtime.cs");
+/// Version of `Microsoft.CodeAnalysis.CSharp` used by the runtime's in-process compiler
+static ROSLYN_VERSION: &str = "4.11.0";
 
 #[allow(dead_code)]
 struct Paths {
@@ -31,27 +38,42 @@ fn main() {
     let constants = output.join("constants.rs");
     let profile = output.parent().unwrap().parent().unwrap().parent().unwrap();
 
-    let dotnet_path = dotnet::get_path().expect("dotnet not found");
+    let config_path = cwd.join("managed.config.json");
+    let config = Config::load(&config_path).expect("invalid managed.config.json");
+
+    let Some((install, framework)) = dotnet::discovery::select(&config.version, config.roll_forward) else {
+        missing_dotnet(
+            &constants,
+            format!(
+                "no installed Microsoft.NETCore.App runtime satisfies the configured version with roll forward policy {}, found: {}",
+                config.roll_forward.as_runtime_config(),
+                dotnet::discovery::installs()
+                    .iter()
+                    .flat_map(|install| install.runtimes.iter().map(|v| format!("{v} ({})", install.root.display())))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+        return;
+    };
+    let has_sdk = !install.sdks.is_empty();
+    let net = format!("net{}.0", framework.major);
+
+    #[cfg(feature = "distribute")]
+    layout_app_local_runtime(&install, &framework, &profile.join(dotnet::discovery::APP_LOCAL));
+
+    let framework = framework.to_string();
 
     let paths = Paths {
-        hostfxr: dotnet_path.join("host").join("fxr"),
-        dotnet: dotnet_path,
+        hostfxr: install.hostfxr_path().expect("hostfxr not found"),
+        dotnet: install.root,
         profile: profile.to_path_buf(),
-        config: cwd.join("managed.config.json"),
+        config: config_path,
         target: cwd.join("target"),
         project: cwd,
         output,
     };
 
-    let config = if paths.config.exists() {
-        let data = std::fs::read_to_string(&paths.config).unwrap();
-        serde_json::from_str::<Config>(&data).unwrap()
-    } else {
-        Config::default()
-    };
-
-    let (framework, net) = get_versions(&paths, &config);
-
     std::fs::write(
         &constants,
         format!(
@@ -65,38 +87,31 @@ fn main() {
 
     let builder = dotnet::Builder::new(&paths.dotnet, &net);
 
-    ensure_runtime(&framework, &net, &paths, &builder);
-}
-
-fn get_versions(paths: &Paths, config: &Config) -> (String, String) {
-    let mut hostfxr_versions = paths
-        .hostfxr
-        .read_dir()
-        .expect("host/fxr not found")
-        .filter_map(Result::ok)
-        .map(|v| v.file_name().to_string_lossy().to_string())
-        .collect::<Vec<_>>();
+    ensure_runtime(&framework, &net, &config, &paths, &builder, has_sdk);
 
-    hostfxr_versions.sort();
+    #[cfg(feature = "distribute")]
+    package_managed(&framework, &net, &config, &paths, builder);
+}
 
-    let framework = match &config.version {
-        Version::Net(n) => hostfxr_versions
-            .iter()
-            .filter(|v| v.starts_with(&n.to_string()))
-            .collect::<Vec<_>>()
-            .last()
-            .cloned(),
-        Version::Framework(f) => hostfxr_versions.iter().find(|v| f == *v),
+/// Distributed games need .NET to be packaged, other builds only warn so the crate still compiles
+/// and the runtime fails to start instead
+fn missing_dotnet(constants: &std::path::Path, reason: String) {
+    if cfg!(feature = "distribute") {
+        panic!("{reason}");
     }
-    .expect("failed to resolve a framework version");
-
-    (
-        framework.to_string(),
-        format!("net{}.0", framework.split_once('.').unwrap().0),
+    log::warn!("{reason}, C# scripting will fail to start");
+    std::fs::write(
+        constants,
+        r#"
+            const FRAMEWORK: &'static str = "";
+            const NET: &'static str = "";
+        "#,
     )
+    .unwrap();
 }
 
-fn format_runtime_csproj(net: &str, framework: &str) -> String {
+fn format_runtime_csproj(net: &str, framework: &str, roll_forward: RollForward) -> String {
+    let roll_forward = roll_forward.as_runtime_config();
     format!(
         r#"<Project Sdk="Microsoft.NET.Sdk">
   <PropertyGroup>
@@ -104,7 +119,7 @@ fn format_runtime_csproj(net: &str, framework: &str) -> String {
     <RuntimeFrameworkVersion>{framework}</RuntimeFrameworkVersion>
     <GenerateRuntimeConfigurationFiles>true</GenerateRuntimeConfigurationFiles>
 
-    <RollForward>Disable</RollForward>
+    <RollForward>{roll_forward}</RollForward>
     <UseWindowsForms>false</UseWindowsForms>
     <UseWPF>false</UseWPF>
     <AllowUnsafeBlocks>true</AllowUnsafeBlocks>
@@ -117,88 +132,157 @@ fn format_runtime_csproj(net: &str, framework: &str) -> String {
   <ItemGroup>
     <FrameworkReference Update="Microsoft.NETCore.App" RuntimeFrameworkVersion="{framework}" />
   </ItemGroup>
+  <ItemGroup>
+    <PackageReference Include="Microsoft.CodeAnalysis.CSharp" Version="{ROSLYN_VERSION}" />
+  </ItemGroup>
 </Project>"#
     )
 }
 
-fn ensure_runtime(framework: &str, net: &str, paths: &Paths, builder: &dotnet::Builder) {
+fn ensure_runtime(
+    framework: &str,
+    net: &str,
+    config: &Config,
+    paths: &Paths,
+    builder: &dotnet::Builder,
+    has_sdk: bool,
+) {
     let runtime_dir = std::env::current_dir()
         .unwrap()
         .join("target")
         .join("runtime");
 
     let runtime_csproj = runtime_dir.join("Runtime.csproj");
-    let runtimeconfig_bin = runtime_dir
-        .join("bin")
-        .join("Release")
-        .join(net)
-        .join("Runtime.runtimeconfig.json");
-    let runtime_dll_bin = runtime_dir
-        .join("bin")
-        .join("Release")
-        .join(net)
-        .join("Runtime.dll");
+    let runtime_bin = runtime_dir.join("bin").join("Release").join(net);
     let runtime_cs = runtime_dir.join("Runtime.cs");
 
-    let runtime_out_dll = paths.profile.join("Runtime.dll");
-    let runtime_out_config = paths.profile.join("Runtime.runtimeconfig.json");
-
     #[cfg(not(feature = "always-build-runtime"))]
-    let needs_rebuild = !runtime_dll_bin.exists()
-        || !runtimeconfig_bin.exists()
+    let needs_rebuild = !runtime_bin.join("Runtime.dll").exists()
+        || !runtime_bin.join("Runtime.runtimeconfig.json").exists()
         || !runtime_csproj.exists()
-        || !std::fs::read_to_string(&runtime_csproj)
-            .unwrap()
-            .contains(&format!(
-                "<RuntimeFrameworkVersion>{framework}</RuntimeFrameworkVersion>",
-            ));
+        || std::fs::read_to_string(&runtime_csproj).unwrap() != format_runtime_csproj(net, framework, config.roll_forward);
     #[cfg(feature = "always-build-runtime")]
     let needs_rebuild = true;
 
     if needs_rebuild {
+        if !has_sdk {
+            if cfg!(feature = "distribute") {
+                panic!("no .NET SDK installed in {}, Runtime.dll can't be built", paths.dotnet.display());
+            }
+            log::warn!(
+                "no .NET SDK installed in {}, Runtime.dll can't be built and C# scripting will fail to start",
+                paths.dotnet.display()
+            );
+            return;
+        }
         if !runtime_dir.exists() {
             std::fs::create_dir(&runtime_dir).unwrap();
         }
 
-        std::fs::write(&runtime_csproj, format_runtime_csproj(net, framework)).unwrap();
+        std::fs::write(&runtime_csproj, format_runtime_csproj(net, framework, config.roll_forward)).unwrap();
 
         std::fs::write(&runtime_cs, RUNTIME_CS).unwrap();
 
         _ = builder.build(&runtime_csproj).unwrap();
+    }
 
-        log::debug!(
-            "[copy] {} to {}",
-            runtimeconfig_bin.strip_prefix(&paths.project).unwrap().display(),
-            runtime_out_config.strip_prefix(&paths.project).unwrap().display()
-        );
-        std::fs::copy(&runtimeconfig_bin, &runtime_out_config).unwrap();
-        log::debug!(
-            "[copy] {} to {}",
-            runtime_dll_bin.strip_prefix(&paths.project).unwrap().display(),
-            runtime_out_dll.strip_prefix(&paths.project).unwrap().display()
-        );
-        std::fs::copy(&runtime_dll_bin, &runtime_out_dll).unwrap();
-    } else {
-        if !runtime_out_config.exists() {
-            log::debug!(
-                "[copy] {} to {}",
-                runtimeconfig_bin.strip_prefix(&paths.project).unwrap().display(),
-                runtime_out_config.strip_prefix(&paths.project).unwrap().display()
-            );
-            std::fs::copy(&runtimeconfig_bin, &runtime_out_config).unwrap();
+    // Runtime.dll is loaded in an isolated context that resolves its dependencies, like Roslyn,
+    // through Runtime.deps.json so every dll in the output is copied next to the executable
+    let artifacts = runtime_bin
+        .read_dir()
+        .unwrap()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "dll")
+                || path.file_name().is_some_and(|name| {
+                    name == "Runtime.runtimeconfig.json" || name == "Runtime.deps.json"
+                })
+        })
+        .collect::<Vec<_>>();
+
+    // Tests and benches run from `deps` so the runtime is laid out next to them too
+    let deps = paths.profile.join("deps");
+    std::fs::create_dir_all(&deps).unwrap();
+
+    for dir in [&paths.profile, &deps] {
+        for artifact in &artifacts {
+            let out = dir.join(artifact.file_name().unwrap());
+            if needs_rebuild || !out.exists() {
+                log::debug!(
+                    "[copy] {} to {}",
+                    artifact.strip_prefix(&paths.project).unwrap().display(),
+                    out.strip_prefix(&paths.project).unwrap().display()
+                );
+                std::fs::copy(artifact, &out).unwrap();
+            }
         }
+    }
+}
 
-        if !runtime_out_dll.exists() {
-            log::debug!(
-                "[copy] {} to {}",
-                runtime_dll_bin.strip_prefix(&paths.project).unwrap().display(),
-                runtime_out_dll.strip_prefix(&paths.project).unwrap().display()
-            );
-            std::fs::copy(&runtime_dll_bin, &runtime_out_dll).unwrap();
+/// Build the `Engine` and `Scripts` assemblies and package them, with their dependencies, in the
+/// managed output next to the executable
+#[cfg(feature = "distribute")]
+fn package_managed(framework: &str, net: &str, config: &Config, paths: &Paths, builder: dotnet::Builder) {
+    project::generate_projects(config, net, framework).unwrap();
+
+    let builder = builder
+        .with_sources(&config.nuget.sources)
+        .with_packages(config.nuget.packages.as_ref());
+
+    let output = paths.profile.join(&config.output);
+    std::fs::create_dir_all(&output).unwrap();
+
+    for (assembly, project) in [("Engine", &config.engine), ("Scripts", &config.scripts)] {
+        let (name, base) = builder
+            .build(project.root().join(format!("{assembly}.csproj")))
+            .unwrap_or_else(|e| panic!("failed to build {assembly}.csproj: {e}"));
+        std::fs::copy(base.join(format!("{name}.dll")), output.join(format!("{assembly}.dll"))).unwrap();
+        project::copy_dependencies(&base, &output).unwrap();
+    }
+}
+
+/// Copy hostfxr and the selected framework, which includes hostpolicy, from the local install
+/// so the game runs without a global .NET install
+#[cfg(feature = "distribute")]
+fn layout_app_local_runtime(
+    install: &dotnet::discovery::Install,
+    framework: &dotnet::discovery::SemVer,
+    root: &std::path::Path,
+) {
+    let hostfxr = install.hostfxr_path().expect("hostfxr not found");
+    let hostfxr_out = root
+        .join(hostfxr.parent().unwrap().strip_prefix(&install.root).unwrap())
+        .join(hostfxr.file_name().unwrap());
+    copy_if_missing(&hostfxr, &hostfxr_out);
+
+    let shared = install.runtime_path(framework);
+    copy_dir(&shared, &root.join(shared.strip_prefix(&install.root).unwrap()));
+}
+
+#[cfg(feature = "distribute")]
+fn copy_dir(from: &std::path::Path, to: &std::path::Path) {
+    for entry in from.read_dir().unwrap().filter_map(Result::ok) {
+        let path = entry.path();
+        let out = to.join(entry.file_name());
+        if path.is_dir() {
+            copy_dir(&path, &out);
+        } else {
+            copy_if_missing(&path, &out);
         }
     }
 }
 
+#[cfg(feature = "distribute")]
+fn copy_if_missing(from: &std::path::Path, to: &std::path::Path) {
+    if to.exists() {
+        return;
+    }
+    std::fs::create_dir_all(to.parent().unwrap()).unwrap();
+    log::debug!("[copy] {} to {}", from.display(), to.display());
+    std::fs::copy(from, to).unwrap();
+}
+
 struct BuildScriptLogger;
 impl log::Log for BuildScriptLogger {
     fn enabled(&self, _: &Metadata) -> bool { true }
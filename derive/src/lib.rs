@@ -0,0 +1,209 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Fields, LitStr};
+
+/// Share a `#[repr(C)]` struct with C#
+///
+/// Implements `ManagedType`, so references can be passed as arguments, `Deserialize` for values
+/// read back from the managed side and `ManagedStruct` which writes the matching C# struct.
+/// The C# struct is `partial` so methods can be added to it in another file.
+///
+/// The C# type is `Engine.<Name>` unless it is named with `#[managed(name = "Game.Stats")]`.
+/// Every field must implement `ManagedType`.
+#[proc_macro_derive(ManagedStruct, attributes(managed))]
+pub fn derive_managed_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "ManagedStruct can't be generic, the C# struct needs concrete field types",
+        ));
+    }
+
+    let mut repr_c = false;
+    let mut type_name = format!("Engine.{ident}");
+    for attr in &input.attrs {
+        if attr.path().is_ident("repr") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("C") {
+                    repr_c = true;
+                } else if meta.path.is_ident("packed") {
+                    return Err(meta.error(
+                        "ManagedStruct can't be packed, the C# struct is laid out with natural alignment",
+                    ));
+                }
+                // Skip arguments like `align(8)`
+                if meta.input.peek(syn::token::Paren) {
+                    let arguments;
+                    syn::parenthesized!(arguments in meta.input);
+                    arguments.parse::<proc_macro2::TokenStream>()?;
+                }
+                Ok(())
+            })?;
+        } else if attr.path().is_ident("managed") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    type_name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name = \"Namespace.Type\"`"))
+                }
+            })?;
+        }
+    }
+    if !repr_c {
+        return Err(syn::Error::new(
+            ident.span(),
+            "ManagedStruct requires #[repr(C)] so the layout matches the C# struct",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new(ident.span(), "ManagedStruct requires named fields")),
+        },
+        _ => return Err(syn::Error::new(ident.span(), "ManagedStruct can only be derived for structs")),
+    };
+
+    let names = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect::<Vec<_>>();
+    let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+    let (namespace, name) = match type_name.rsplit_once('.') {
+        Some((namespace, name)) => (format!("namespace {namespace};\n\n"), name.to_string()),
+        None => (String::new(), type_name.clone()),
+    };
+    // Used as a format string by the generated code so braces are escaped
+    let header = format!(
+        "// <auto-generated>\n// Generated from `{ident}` by #[derive(ManagedStruct)], changes are overwritten\n// </auto-generated>\nusing System.Runtime.InteropServices;\n{namespace}[StructLayout(LayoutKind.Sequential)]\npublic partial struct {name}\n{{{{\n"
+    );
+    let field_lines = names
+        .iter()
+        .map(|name| format!("    public {{}} {name};\n"))
+        .collect::<String>();
+
+    // Values are read back as JSON with the C# field names, which are the rust field names
+    let shadow = format_ident!("__{ident}Managed");
+
+    Ok(quote! {
+        impl ::bevy_cs_managed::runtime::ManagedType for #ident {
+            const TYPE_NAME: &'static str = #type_name;
+        }
+
+        impl ::bevy_cs_managed::runtime::ManagedStruct for #ident {
+            fn csharp_source() -> ::std::string::String {
+                ::std::format!(
+                    concat!(#header, #field_lines, "}}\n"),
+                    #(<#types as ::bevy_cs_managed::runtime::ManagedType>::TYPE_NAME),*
+                )
+            }
+        }
+
+        const _: () = {
+            #[derive(::bevy_cs_managed::__private::serde::Deserialize)]
+            #[serde(crate = "::bevy_cs_managed::__private::serde")]
+            struct #shadow {
+                #(#names: #types),*
+            }
+
+            impl<'de> ::bevy_cs_managed::__private::serde::Deserialize<'de> for #ident {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: ::bevy_cs_managed::__private::serde::Deserializer<'de>,
+                {
+                    let value = <#shadow as ::bevy_cs_managed::__private::serde::Deserialize>::deserialize(deserializer)?;
+                    Ok(Self { #(#names: value.#names),* })
+                }
+            }
+        };
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn error(input: DeriveInput) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn repr_c() {
+        assert!(expand(parse_quote! {
+            #[repr(C)]
+            struct Vector3 { x: f32, y: f32, z: f32 }
+        })
+        .is_ok());
+        assert!(expand(parse_quote! {
+            #[repr(C, align(16))]
+            struct Vector4 { x: f32, y: f32, z: f32, w: f32 }
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn requires_repr_c() {
+        let message = error(parse_quote! {
+            struct Vector3 { x: f32, y: f32, z: f32 }
+        });
+        assert!(message.contains("requires #[repr(C)]"), "{message}");
+
+        let message = error(parse_quote! {
+            #[repr(Rust)]
+            struct Vector3 { x: f32, y: f32, z: f32 }
+        });
+        assert!(message.contains("requires #[repr(C)]"), "{message}");
+    }
+
+    #[test]
+    fn rejects_packed() {
+        for input in [
+            parse_quote! {
+                #[repr(C, packed)]
+                struct Header { tag: u8, length: u32 }
+            },
+            parse_quote! {
+                #[repr(C, packed(2))]
+                struct Header { tag: u8, length: u32 }
+            },
+            parse_quote! {
+                #[repr(C)]
+                #[repr(packed)]
+                struct Header { tag: u8, length: u32 }
+            },
+        ] {
+            let message = error(input);
+            assert!(message.contains("can't be packed"), "{message}");
+        }
+    }
+
+    #[test]
+    fn rejects_unsupported_items() {
+        let message = error(parse_quote! {
+            #[repr(C)]
+            struct Pair(f32, f32);
+        });
+        assert!(message.contains("named fields"), "{message}");
+
+        let message = error(parse_quote! {
+            #[repr(C)]
+            enum Kind { A, B }
+        });
+        assert!(message.contains("only be derived for structs"), "{message}");
+
+        let message = error(parse_quote! {
+            #[repr(C)]
+            struct Wrapper<T> { value: T }
+        });
+        assert!(message.contains("can't be generic"), "{message}");
+    }
+}
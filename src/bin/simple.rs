@@ -1,10 +1,12 @@
-use bevy_cs_managed::{CSharpPlugin, Runtime, Script};
+use bevy_cs_managed::{CSharpPlugin, ManagedStruct, ManagedType, Runtime, Script};
 
+use bytemuck::{Pod, Zeroable};
 use bevy::{ecs::{lifecycle::HookContext, world::DeferredWorld}, prelude::*};
-use serde::Deserialize;
 
+// The matching `Engine.Vector3` C# struct is generated into the engine project so the layouts can't drift
 #[allow(dead_code)]
-#[derive(Clone, Deserialize)]
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable, ManagedStruct)]
 struct Vector3 {
     x: f32,
     y: f32,
@@ -34,13 +36,13 @@ fn setup_scripts(world: &mut World) {
         .on_add(awake);
 }
 
-fn spawn_scripts(mut commands: Commands, mut runtime: ResMut<Runtime>) {
+fn spawn_scripts(mut commands: Commands, runtime: Res<Runtime>) {
     //┌─This is the fullname of the desired scripts class.
-    //┆  The scripts class is resolved by matching a global namespace class with the same name as the file.
+    //┆  Classes deriving from `Engine.Behaviour` or marked with `[Script]` are registered automatically.
     //┆
     //┆  // Player.cs
     //┆  using Engine;
-    //┆  class Player
+    //┆  class Player : Behaviour
     //┆  {
     //┆     public Vector3 Position;
     //┆  }
@@ -52,21 +54,33 @@ fn spawn_scripts(mut commands: Commands, mut runtime: ResMut<Runtime>) {
     //┆
     //┆ Warning: The property must have a public setter
     //┆
-    //┆┌─ Can pass any reference to a value as long as the structure matches
+    //┆┌─ Can pass a reference to any `ManagedType` as long as the structure matches
     //┆┆    the expected type in the C# method parameter
     //┆└──────────────────────────────────┐
     //└────┐                              │
     script.set_property_value("Position", &Vector3 { x: 1.2, y: 2.4, z: 3.6 }).unwrap();
 
     println!("Player {{");
-    // MetaData, fields and properties, are cached when the class type is loaded and
-    //    can be used to for reflection
-    let metadata = runtime.get_meta_data(&script);
-    for field in &metadata.fields {
-        println!("    {} = {:?}", field.name, script.get_field_value::<Vector3>(&field.name));
+    // Type info describes the fields and properties with their C# types and can be used for reflection
+    let info = runtime.get_type_info(&script).unwrap();
+    for field in info.fields.iter().filter(|field| field.is_public && !field.is_static) {
+        if field.r#type == Vector3::TYPE_NAME {
+            //┌─ Blittable values, like `Vector3`, are copied straight into the given value
+            //┆    when the field has that type, other values are read as JSON
+            //└──────────────────┐
+            let mut value = Vector3::zeroed();
+            script.get_field_value_into(&field.name, &mut value).unwrap();
+            println!("    {} = {:?}", field.name, value);
+        } else {
+            println!("    {} = {:?}", field.name, script.get_field_value::<serde_json::Value>(&field.name));
+        }
     }
-    for prop in &metadata.properties {
-        println!("    {} = {:?}", prop.name, script.get_property_value::<Vector3>(&prop.name));
+    for prop in info.properties.iter().filter(|prop| prop.can_read && !prop.is_static) {
+        if prop.r#type == Vector3::TYPE_NAME {
+            println!("    {} = {:?}", prop.name, script.get_property_value::<Vector3>(&prop.name));
+        } else {
+            println!("    {} = {:?}", prop.name, script.get_property_value::<serde_json::Value>(&prop.name));
+        }
     }
     println!("}}");
 
@@ -80,23 +94,23 @@ fn update(
 ) {
     let dt = delta.delta_secs();
 
-    for script in &query {
-        //┌─ Lookup the Update method that has 1 arguments
-        //┆
-        //┆ When invoked the arguments can be passed as a single value reference if there is 1 arg
-        //┆     or as a tuple of value references for multiple args.
-        //┆
-        //└──────────────────────────────────────────────────┬─────────┐
-        if let Ok(Some(update)) = runtime.get_method(script, "Update", 1) {
-            update.invoke(&dt).unwrap();
-        }
+    //┌─ Invoke the Update method that has 1 argument on every script with a single call into the runtime
+    //┆    scripts without an Update method are skipped
+    //┆
+    //┆ The arguments can be passed as a single value reference if there is 1 arg
+    //┆     or as a tuple of value references for multiple args. Every script receives the same arguments.
+    //┆
+    //┆ Scripts that fail, e.g. by throwing, are returned with the error while the others keep running
+    //└────────────────────────────────────┬──────────┬───────┐
+    for (position, error) in runtime.invoke_all(query, "Update", &dt).unwrap() {
+        eprintln!("script {position} failed to update: {error}");
     }
 }
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(CSharpPlugin)
+        .add_plugins(CSharpPlugin::default().managed_struct::<Vector3>())
         // User is given complete control on how the scripts should be called and manipulated
         //   the crate handles bootstrapping the runtime and managing script references like
         //   classes, objects, methods, etc.
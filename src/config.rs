@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use serde::Deserialize;
 
 pub enum Version {
@@ -54,8 +56,181 @@ impl Default for Version {
     }
 }
 
+/// How the runtime version may roll forward to another installed version,
+/// matching `rollForward` in a `runtimeconfig.json`
+///
+/// The requested version is `X.0.0` for [`Version::Net`] and the exact framework for [`Version::Framework`].
+/// Pre-release runtimes are only used when a pre-release is requested.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RollForward {
+    /// Only the requested version
+    #[serde(alias = "Disable")]
+    Disable,
+    /// The latest patch of the requested major and minor version
+    #[default]
+    #[serde(alias = "LatestPatch")]
+    Patch,
+    /// The latest patch of the lowest minor version, of the requested major version, that satisfies the request
+    #[serde(alias = "Minor")]
+    Minor,
+    /// Same as [`RollForward::Minor`] but a higher major version is used when no minor version satisfies the request
+    #[serde(alias = "Major")]
+    Major,
+}
+
+#[allow(dead_code)]
+impl RollForward {
+    /// Name of the policy in a `runtimeconfig.json` or `<RollForward>` project property
+    pub fn as_runtime_config(&self) -> &'static str {
+        match self {
+            Self::Disable => "Disable",
+            Self::Patch => "LatestPatch",
+            Self::Minor => "Minor",
+            Self::Major => "Major",
+        }
+    }
+}
+
+/// Contents of `managed.config.json` describing how the managed projects are laid out
+///
+/// All paths are relative to the current working directory unless stated otherwise.
+///
+/// # Example
+/// ```json
+/// {
+///   "version": "net8.0",
+///   "rollForward": "patch",
+///   "output": "managed",
+///   "solution": "assets",
+///   "engine": { "roots": ["assets/engine"] },
+///   "scripts": {
+///     "roots": ["assets/scripts", "assets/shared"],
+///     "packages": [{ "name": "Newtonsoft.Json", "version": "13.0.3" }],
+///     "defines": ["GAME"],
+///     "langVersion": "latest"
+///   },
+///   "nuget": { "sources": ["packages"], "packages": "target/nuget" }
+/// }
+/// ```
 #[allow(dead_code)]
-#[derive(Default, serde::Deserialize)]
+#[derive(serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Config {
     pub version: Version,
+    /// Which installed runtimes satisfy `version` on the machine the game runs on
+    pub roll_forward: RollForward,
+    /// Directory, relative to the executable, that `Engine.dll` and `Scripts.dll` are placed in
+    pub output: PathBuf,
+    /// Directory the solution, `Directory.Build.props` and `.editorconfig` are generated in
+    pub solution: PathBuf,
+    pub engine: Project,
+    pub scripts: Project,
+    pub nuget: NuGet,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: Version::default(),
+            roll_forward: RollForward::default(),
+            output: PathBuf::from("managed"),
+            solution: PathBuf::from("assets"),
+            engine: Project::with_root("assets/engine"),
+            scripts: Project::with_root("assets/scripts"),
+            nuget: NuGet::default(),
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Config {
+    /// Read the config at `path` falling back to the default layout when the file doesn't exist
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = std::fs::read_to_string(path)?;
+        let mut config = serde_json::from_str::<Config>(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        if config.engine.roots.is_empty() {
+            config.engine.roots.push(PathBuf::from("assets/engine"));
+        }
+        if config.scripts.roots.is_empty() {
+            config.scripts.roots.push(PathBuf::from("assets/scripts"));
+        }
+
+        Ok(config)
+    }
+}
+
+/// Settings for a generated C# project
+#[allow(dead_code)]
+#[derive(Clone, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Project {
+    /// Directories containing `.cs` sources. The project file is generated in the first root.
+    pub roots: Vec<PathBuf>,
+    /// Additional `.csproj` files the project references
+    pub references: Vec<PathBuf>,
+    /// NuGet packages the project references
+    pub packages: Vec<Package>,
+    /// Extra conditional compilation symbols
+    pub defines: Vec<String>,
+    /// Value of `<Nullable>`, e.g. `enable`, `disable`, `warnings` or `annotations`
+    pub nullable: String,
+    /// Value of `<LangVersion>`, the SDK default is used when not set
+    pub lang_version: Option<String>,
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            references: Vec::new(),
+            packages: Vec::new(),
+            defines: Vec::new(),
+            nullable: "enable".into(),
+            lang_version: None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl Project {
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            roots: vec![root.into()],
+            ..Default::default()
+        }
+    }
+
+    /// Directory the project file is generated in
+    pub fn root(&self) -> &Path {
+        self.roots.first().map(PathBuf::as_path).unwrap_or(Path::new("."))
+    }
+}
+
+/// NuGet package reference
+#[allow(dead_code)]
+#[derive(Clone, serde::Deserialize)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+}
+
+/// NuGet restore settings shared by all generated projects
+#[allow(dead_code)]
+#[derive(Clone, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct NuGet {
+    /// Package sources to restore from, e.g. a local feed directory.
+    /// The `NuGet.config` sources are used when empty.
+    pub sources: Vec<String>,
+    /// Directory restored packages are stored in, e.g. an offline cache.
+    /// The global packages folder is used when not set.
+    pub packages: Option<PathBuf>,
 }
@@ -12,6 +12,12 @@ pub struct Builder {
     /// Path to the dotnet executable
     dotnet: PathBuf,
     net: String,
+    /// Build configuration, `Release` by default
+    configuration: String,
+    /// NuGet sources used when restoring packages
+    sources: Vec<String>,
+    /// Directory NuGet packages are restored to
+    packages: Option<PathBuf>,
 }
 
 impl Builder {
@@ -22,15 +28,37 @@ impl Builder {
             #[cfg(not(target_os = "windows"))]
             dotnet: base.as_ref().join("dotnet"),
             net: net.as_ref().to_string(),
+            configuration: "Release".into(),
+            sources: Vec::new(),
+            packages: None,
         }
     }
 
+    #[allow(dead_code)]
+    pub fn with_configuration(mut self, configuration: impl AsRef<str>) -> Self {
+        self.configuration = configuration.as_ref().to_string();
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_sources(mut self, sources: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.sources = sources.into_iter().map(|v| v.as_ref().to_string()).collect();
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_packages(mut self, packages: Option<impl AsRef<Path>>) -> Self {
+        self.packages = packages.map(|v| v.as_ref().to_path_buf());
+        self
+    }
+
     pub fn build(&self, project_file: impl AsRef<Path>) -> std::io::Result<(String, PathBuf)> {
         let csproj = project_file.as_ref();
         let base = csproj.parent().unwrap();
 
         let data = std::fs::read_to_string(csproj)?;
-        let project: Project = serde_xml_rs::from_str(&data).unwrap();
+        let project: Project = serde_xml_rs::from_str(&data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
 
         let name = project.property_group
             .iter()
@@ -43,24 +71,25 @@ impl Builder {
 
         let now = std::time::Instant::now();
         let build_log = base.join("build.log");
-        let result = std::process::Command::new(&self.dotnet)
+        let mut command = std::process::Command::new(&self.dotnet);
+        command
             .arg("build")
             .arg(csproj)
-            .args(["-c", "Release"])
+            .args(["-c", self.configuration.as_str()])
             .arg("-flp:v=q")
             .arg(format!("-flp:logfile={}", build_log.display()))
+            .args(self.sources.iter().flat_map(|v| ["--source", v.as_str()]));
+        if let Some(packages) = &self.packages {
+            command.arg(format!("-p:RestorePackagesPath={}", packages.display()));
+        }
+        let result = command
             .stdout(Stdio::piped())
-            .output()
-            .unwrap();
+            .output()?;
 
         log::debug!("[compile] {name} {:.3} s", now.elapsed().as_secs_f64());
 
-        if !result.status.success() {
-            panic!("[build] failure: {}",  String::from_utf8_lossy(&result.stdout));
-        }
-
         if build_log.exists() {
-            let diag = std::fs::read_to_string(&build_log).unwrap();
+            let diag = std::fs::read_to_string(&build_log)?;
             let pattern = regex::Regex::new(
                 r"(.+)\((\d+),(\d+)\): (warning|error) (CS\d+): (.+) \[[^\]]+\]",
             )
@@ -84,6 +113,13 @@ impl Builder {
                 });
         }
 
-        Ok((name, base.join("bin").join("Release").join(&self.net)))
+        if !result.status.success() {
+            return Err(std::io::Error::other(format!(
+                "[build] {name} failure: {}",
+                String::from_utf8_lossy(&result.stdout)
+            )));
+        }
+
+        Ok((name, base.join("bin").join(&self.configuration).join(&self.net)))
     }
 }
@@ -0,0 +1,449 @@
+use std::{
+    cmp::Ordering,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::config::{RollForward, Version};
+
+#[cfg(target_os = "windows")]
+pub const EXECUTABLE: &str = "dotnet.exe";
+#[cfg(not(target_os = "windows"))]
+pub const EXECUTABLE: &str = "dotnet";
+
+#[cfg(target_os = "windows")]
+pub const HOSTFXR: &str = "hostfxr.dll";
+#[cfg(target_os = "macos")]
+pub const HOSTFXR: &str = "libhostfxr.dylib";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub const HOSTFXR: &str = "libhostfxr.so";
+
+/// Directory next to the executable an app-local runtime is laid out in for distribution
+pub const APP_LOCAL: &str = "dotnet";
+
+/// Architecture name used by `DOTNET_ROOT_<ARCH>` and `/etc/dotnet/install_location_<arch>`
+#[cfg(target_arch = "x86_64")]
+const ARCH: &str = "x64";
+#[cfg(target_arch = "aarch64")]
+const ARCH: &str = "arm64";
+#[cfg(target_arch = "x86")]
+const ARCH: &str = "x86";
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64", target_arch = "x86")))]
+const ARCH: &str = "arm";
+
+/// Semantic version of an installed runtime, sdk or hostfxr, e.g. `9.0.10` or `10.0.0-rc.1.25451.107`
+#[derive(Debug, Clone)]
+pub struct SemVer {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub pre: Option<String>,
+}
+
+impl FromStr for SemVer {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        // Build metadata doesn't take part in precedence
+        let value = value.split_once('+').map(|(v, _)| v).unwrap_or(value);
+        let (version, pre) = match value.split_once('-') {
+            Some((version, pre)) => (version, Some(pre.to_string())),
+            None => (value, None),
+        };
+
+        let mut parts = version.split('.').map(u32::from_str);
+        let major = parts.next().ok_or(())?.map_err(|_| ())?;
+        let minor = parts.next().ok_or(())?.map_err(|_| ())?;
+        let patch = parts.next().ok_or(())?.map_err(|_| ())?;
+        if parts.next().is_some() {
+            return Err(());
+        }
+
+        Ok(Self { major, minor, patch, pre })
+    }
+}
+
+impl std::fmt::Display for SemVer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(pre) = &self.pre {
+            write!(f, "-{pre}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.pre, &other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_prerelease(a, b),
+            })
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SemVer {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SemVer {}
+
+/// Compare dot separated pre-release identifiers, numeric identifiers have lower precedence
+fn compare_prerelease(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let ord = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+}
+
+/// A dotnet installation directory and what is installed in it
+#[derive(Debug, Clone)]
+pub struct Install {
+    pub root: PathBuf,
+    /// Installed `Microsoft.NETCore.App` runtimes, oldest first
+    pub runtimes: Vec<SemVer>,
+    /// Installed sdks, oldest first
+    pub sdks: Vec<SemVer>,
+    /// Installed versions of hostfxr, oldest first
+    pub hostfxr: Vec<SemVer>,
+}
+
+impl Install {
+    /// Inspect `root`, `None` when it isn't a dotnet installation
+    pub fn new(root: impl Into<PathBuf>) -> Option<Self> {
+        let root = root.into();
+        let hostfxr = list_versions(&root.join("host").join("fxr"));
+        if hostfxr.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            runtimes: list_versions(&root.join("shared").join("Microsoft.NETCore.App")),
+            sdks: list_versions(&root.join("sdk")),
+            hostfxr,
+            root,
+        })
+    }
+
+    pub fn executable(&self) -> PathBuf {
+        self.root.join(EXECUTABLE)
+    }
+
+    /// Path to the newest hostfxr library, hostfxr is backwards compatible with older runtimes
+    pub fn hostfxr_path(&self) -> Option<PathBuf> {
+        self.hostfxr.last().map(|version| {
+            self.root
+                .join("host")
+                .join("fxr")
+                .join(version.to_string())
+                .join(HOSTFXR)
+        })
+    }
+
+    /// Directory of an installed `Microsoft.NETCore.App` runtime
+    pub fn runtime_path(&self, version: &SemVer) -> PathBuf {
+        self.root
+            .join("shared")
+            .join("Microsoft.NETCore.App")
+            .join(version.to_string())
+    }
+
+    /// The installed runtime satisfying `requested` under the roll forward policy
+    pub fn resolve_runtime(&self, requested: &SemVer, roll_forward: RollForward) -> Option<&SemVer> {
+        let candidates = self
+            .runtimes
+            .iter()
+            .filter(|v| *v >= requested && (requested.pre.is_some() || v.pre.is_none()));
+        let latest_patch = |major: u32, minor: u32| {
+            candidates
+                .clone()
+                .filter(|v| v.major == major && v.minor == minor)
+                .max()
+        };
+
+        match roll_forward {
+            RollForward::Disable => self.runtimes.iter().find(|v| *v == requested),
+            RollForward::Patch => latest_patch(requested.major, requested.minor),
+            RollForward::Minor => candidates
+                .clone()
+                .filter(|v| v.major == requested.major)
+                .min()
+                .and_then(|v| latest_patch(v.major, v.minor)),
+            RollForward::Major => candidates
+                .clone()
+                .min()
+                .and_then(|v| latest_patch(v.major, v.minor)),
+        }
+    }
+
+    /// The installed runtime satisfying `version` under the roll forward policy, see [`requested`]
+    pub fn select_runtime(&self, version: &Version, roll_forward: RollForward) -> Option<&SemVer> {
+        self.resolve_runtime(&requested(version)?, roll_forward)
+    }
+}
+
+/// Lowest runtime version `version` asks for: `X.0.0` for [`Version::Net`] and the exact framework otherwise
+pub fn requested(version: &Version) -> Option<SemVer> {
+    match version {
+        Version::Net(major) => Some(SemVer {
+            major: u32::from(*major),
+            minor: 0,
+            patch: 0,
+            pre: None,
+        }),
+        Version::Framework(framework) => framework.parse().ok(),
+    }
+}
+
+/// Parsed names of the version directories in `dir`, oldest first
+fn list_versions(dir: &Path) -> Vec<SemVer> {
+    let Ok(entries) = dir.read_dir() else {
+        return Vec::new();
+    };
+
+    let mut versions = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<SemVer>().ok())
+        .collect::<Vec<_>>();
+    versions.sort();
+    versions
+}
+
+/// Possible installation directories in the order the dotnet host looks for them
+fn candidates() -> Vec<PathBuf> {
+    let mut out = Vec::new();
+
+    out.extend(std::env::var_os(format!("DOTNET_ROOT_{}", ARCH.to_ascii_uppercase())).map(PathBuf::from));
+    out.extend(std::env::var_os("DOTNET_ROOT").map(PathBuf::from));
+
+    if let Some(path) = std::env::var_os("PATH") {
+        out.extend(
+            std::env::split_paths(&path)
+                .map(|dir| dir.join(EXECUTABLE))
+                .filter(|exe| exe.is_file())
+                // Package managers usually symlink the executable into a `bin` directory
+                .filter_map(|exe| dunce::canonicalize(exe).ok())
+                .filter_map(|exe| exe.parent().map(Path::to_path_buf)),
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    for file in [
+        format!("/etc/dotnet/install_location_{ARCH}"),
+        "/etc/dotnet/install_location".to_string(),
+    ] {
+        if let Ok(data) = std::fs::read_to_string(file) {
+            out.extend(
+                data.lines()
+                    .next()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty())
+                    .map(PathBuf::from),
+            );
+        }
+    }
+
+    #[cfg(all(target_os = "windows", not(target_arch = "x86")))]
+    out.push(PathBuf::from("C:\\Program Files\\dotnet"));
+    #[cfg(all(target_os = "windows", target_arch = "x86"))]
+    out.push(PathBuf::from("C:\\Program Files (x86)\\dotnet"));
+    #[cfg(target_os = "linux")]
+    out.extend([
+        PathBuf::from("/usr/share/dotnet"),
+        PathBuf::from("/usr/lib/dotnet"),
+        PathBuf::from("/usr/lib64/dotnet"),
+    ]);
+    #[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+    out.push(PathBuf::from("/usr/local/share/dotnet/x64"));
+    #[cfg(target_os = "macos")]
+    out.push(PathBuf::from("/usr/local/share/dotnet"));
+
+    out.extend(dirs::home_dir().map(|home| home.join(".dotnet")));
+
+    out
+}
+
+/// Every dotnet installation found on the system, in priority order
+pub fn installs() -> Vec<Install> {
+    let mut seen = Vec::new();
+    candidates()
+        .into_iter()
+        .filter_map(|root| dunce::canonicalize(root).ok())
+        .filter(|root| {
+            if seen.contains(root) {
+                false
+            } else {
+                seen.push(root.clone());
+                true
+            }
+        })
+        .filter_map(Install::new)
+        .collect()
+}
+
+/// The first installation with a runtime satisfying `version` under the roll forward policy,
+/// along with that runtime's version
+pub fn select(version: &Version, roll_forward: RollForward) -> Option<(Install, SemVer)> {
+    installs().into_iter().find_map(|install| {
+        let runtime = install.select_runtime(version, roll_forward)?.clone();
+        Some((install, runtime))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(value: &str) -> SemVer {
+        value.parse().unwrap()
+    }
+
+    fn install(runtimes: &[&str]) -> Install {
+        let mut runtimes = runtimes.iter().map(|r| v(r)).collect::<Vec<_>>();
+        runtimes.sort();
+        Install {
+            root: PathBuf::from("dotnet"),
+            runtimes,
+            sdks: Vec::new(),
+            hostfxr: vec![v("10.0.1")],
+        }
+    }
+
+    fn resolve(install: &Install, requested: &str, roll_forward: RollForward) -> Option<String> {
+        install.resolve_runtime(&v(requested), roll_forward).map(SemVer::to_string)
+    }
+
+    #[test]
+    fn parse() {
+        let version = v("9.0.10");
+        assert_eq!((version.major, version.minor, version.patch, version.pre), (9, 0, 10, None));
+
+        let version = v("10.0.0-rc.1.25451.107");
+        assert_eq!((version.major, version.minor, version.patch), (10, 0, 0));
+        assert_eq!(version.pre.as_deref(), Some("rc.1.25451.107"));
+        assert_eq!(version.to_string(), "10.0.0-rc.1.25451.107");
+
+        assert_eq!(v("8.0.1+abc123").to_string(), "8.0.1");
+
+        for invalid in ["", "9", "9.0", "9.0.x", "9.0.1.2", "v9.0.1"] {
+            assert!(invalid.parse::<SemVer>().is_err(), "{invalid} should not parse");
+        }
+    }
+
+    #[test]
+    fn order_is_numeric() {
+        assert!(v("10.0.0") > v("9.0.10"));
+        assert!(v("9.0.10") > v("9.0.9"));
+        assert!(v("9.1.0") > v("9.0.10"));
+        assert_eq!(v("8.0.1+abc"), v("8.0.1+def"));
+
+        let mut versions = ["9.0.10", "10.0.0", "9.0.2", "8.0.21"].map(v);
+        versions.sort();
+        assert_eq!(versions.map(|v| v.to_string()), ["8.0.21", "9.0.2", "9.0.10", "10.0.0"]);
+    }
+
+    #[test]
+    fn order_prereleases() {
+        assert!(v("10.0.0-rc.1") < v("10.0.0"));
+        assert!(v("10.0.0-rc.1") > v("9.0.10"));
+        assert!(v("10.0.0-preview.7") < v("10.0.0-rc.1"));
+        assert!(v("10.0.0-rc.1") < v("10.0.0-rc.2"));
+        assert!(v("10.0.0-rc.2") < v("10.0.0-rc.10"));
+        assert!(v("10.0.0-rc.1") < v("10.0.0-rc.1.25451.107"));
+        // Numeric identifiers have lower precedence than alphanumeric ones
+        assert!(v("10.0.0-1") < v("10.0.0-alpha"));
+    }
+
+    const RUNTIMES: &[&str] = &["8.0.1", "8.0.5", "8.1.2", "9.0.0", "9.0.3", "10.0.0-rc.1", "10.0.1"];
+
+    #[test]
+    fn roll_forward_disable() {
+        let install = install(RUNTIMES);
+        assert_eq!(resolve(&install, "8.0.1", RollForward::Disable).as_deref(), Some("8.0.1"));
+        assert_eq!(resolve(&install, "8.0.0", RollForward::Disable), None);
+        assert_eq!(resolve(&install, "10.0.0-rc.1", RollForward::Disable).as_deref(), Some("10.0.0-rc.1"));
+    }
+
+    #[test]
+    fn roll_forward_patch() {
+        let install = install(RUNTIMES);
+        assert_eq!(resolve(&install, "8.0.0", RollForward::Patch).as_deref(), Some("8.0.5"));
+        assert_eq!(resolve(&install, "8.0.3", RollForward::Patch).as_deref(), Some("8.0.5"));
+        assert_eq!(resolve(&install, "8.0.6", RollForward::Patch), None);
+        assert_eq!(resolve(&install, "8.2.0", RollForward::Patch), None);
+    }
+
+    #[test]
+    fn roll_forward_minor() {
+        let install = install(RUNTIMES);
+        assert_eq!(resolve(&install, "8.0.0", RollForward::Minor).as_deref(), Some("8.0.5"));
+        assert_eq!(resolve(&install, "8.0.6", RollForward::Minor).as_deref(), Some("8.1.2"));
+        assert_eq!(resolve(&install, "8.2.0", RollForward::Minor), None);
+    }
+
+    #[test]
+    fn roll_forward_major() {
+        let install = install(RUNTIMES);
+        assert_eq!(resolve(&install, "8.0.0", RollForward::Major).as_deref(), Some("8.0.5"));
+        assert_eq!(resolve(&install, "8.2.0", RollForward::Major).as_deref(), Some("9.0.3"));
+        assert_eq!(resolve(&install, "9.1.0", RollForward::Major).as_deref(), Some("10.0.1"));
+        assert_eq!(resolve(&install, "11.0.0", RollForward::Major), None);
+    }
+
+    #[test]
+    fn prereleases_are_only_used_when_requested() {
+        let install = install(&["9.0.0", "9.0.1-rc.1", "10.0.0-rc.1"]);
+        assert_eq!(resolve(&install, "9.0.0", RollForward::Patch).as_deref(), Some("9.0.0"));
+        assert_eq!(resolve(&install, "9.1.0", RollForward::Major), None);
+        assert_eq!(resolve(&install, "9.0.1-rc.1", RollForward::Patch).as_deref(), Some("9.0.1-rc.1"));
+        assert_eq!(resolve(&install, "10.0.0-preview.1", RollForward::Major).as_deref(), Some("10.0.0-rc.1"));
+    }
+
+    #[test]
+    fn select_runtime_by_version() {
+        let install = install(&["8.0.21", "9.0.2", "9.0.10", "10.0.0"]);
+        let select = |version: Version, roll_forward| install.select_runtime(&version, roll_forward).map(SemVer::to_string);
+        assert_eq!(select(Version::Net(9), RollForward::Patch).as_deref(), Some("9.0.10"));
+        assert_eq!(select(Version::Net(10), RollForward::Patch).as_deref(), Some("10.0.0"));
+        assert_eq!(select(Version::Net(9), RollForward::Disable), None);
+        assert_eq!(select(Version::Net(7), RollForward::Patch), None);
+        assert_eq!(select(Version::Net(7), RollForward::Major).as_deref(), Some("8.0.21"));
+        assert_eq!(select(Version::Framework("9.0.2".into()), RollForward::Disable).as_deref(), Some("9.0.2"));
+        assert_eq!(select(Version::Framework("9.0.2".into()), RollForward::Patch).as_deref(), Some("9.0.10"));
+        assert_eq!(select(Version::Framework("9.0.3".into()), RollForward::Disable), None);
+    }
+
+    #[test]
+    fn requested_version() {
+        assert_eq!(requested(&Version::Net(8)), Some(v("8.0.0")));
+        assert_eq!(requested(&Version::Framework("9.0.10".into())), Some(v("9.0.10")));
+        assert_eq!(requested(&Version::Framework("latest".into())), None);
+    }
+}
@@ -1,3 +1,7 @@
+use std::path::PathBuf;
+
+use crate::{config::RollForward, hostfxr::describe_status};
+
 #[derive(Debug)]
 pub enum Error {
     ClassNotFound,
@@ -11,7 +15,27 @@ pub enum Error {
     PathNotFound,
     AssemblyNotLoaded,
     ClassNotRegistered,
+    /// The managed value has to be serialized, it contains references or isn't a value type
+    NotBlittable,
+    /// The managed value is blittable but its size differs from the rust type
+    SizeMismatch,
+    /// The object isn't an instance of the class a field or property accessor was resolved for
+    InvalidInstance,
+    /// The managed method threw an exception
+    InvocationFailed,
     UnknownManaged,
+    CompilationFailed,
+    DotnetNotFound,
+    HostfxrNotFound(PathBuf),
+    FrameworkNotFound { requested: String, roll_forward: RollForward },
+    RuntimeConfigInvalid { code: i32 },
+    DelegateUnavailable { name: String, code: i32 },
+    RuntimeAssemblyLoadFailed { code: i32 },
+    /// An argument doesn't match the type or layout of the parameter it is passed to
+    ArgumentTypeMismatch { method: String, position: usize, expected: String, found: String },
+    ArgumentCountMismatch { method: String, expected: usize, found: usize },
+    /// A slice has more elements than a C# array can hold
+    ArrayTooLong { length: usize },
     Io(std::io::Error),
     Json(serde_json::Error),
 }
@@ -30,6 +54,11 @@ impl From<i32> for Error {
             9 => Error::PathNotFound,
             10 => Error::AssemblyNotLoaded,
             11 => Error::ClassNotRegistered,
+            12 => Error::NotBlittable,
+            13 => Error::SizeMismatch,
+            14 => Error::InvalidInstance,
+            15 => Error::InvocationFailed,
+            16 => Error::CompilationFailed,
             _ => Error::UnknownManaged,
         }
     }
@@ -60,7 +89,46 @@ impl std::fmt::Display for Error {
             Self::MissingGetter => write!(f, "property is missing a getter"),
             Self::MissingSetter => write!(f, "property is missing a setter"),
             Self::MissingRequiredArgument => write!(f, "missing required argument: was `null`"),
+            Self::NotBlittable => write!(f, "managed value is not blittable"),
+            Self::SizeMismatch => write!(f, "managed value has a different size than the rust type"),
+            Self::InvalidInstance => write!(f, "object is not an instance of the class the accessor was resolved for"),
+            Self::InvocationFailed => write!(f, "managed method threw an exception"),
             Self::UnknownManaged => write!(f, "an unknown managed c# error occured"),
+            Self::CompilationFailed => write!(f, "failed to compile c# sources"),
+            Self::DotnetNotFound => write!(f, "no dotnet installation found: install the .NET runtime or set DOTNET_ROOT"),
+            Self::HostfxrNotFound(path) => write!(f, "failed to load hostfxr at {}", path.display()),
+            Self::FrameworkNotFound { requested, roll_forward } => write!(
+                f,
+                "no installed Microsoft.NETCore.App runtime satisfies {requested} with roll forward policy {}",
+                roll_forward.as_runtime_config()
+            ),
+            Self::RuntimeConfigInvalid { code } => write!(
+                f,
+                "failed to initialize hostfxr from the runtime config: 0x{code:08X} {}",
+                describe_status(*code)
+            ),
+            Self::DelegateUnavailable { name, code } => write!(
+                f,
+                "failed to fetch runtime delegate `{name}`: 0x{code:08X} {}",
+                describe_status(*code)
+            ),
+            Self::RuntimeAssemblyLoadFailed { code } => write!(
+                f,
+                "failed to load Runtime.dll: 0x{code:08X} {}",
+                describe_status(*code)
+            ),
+            Self::ArgumentTypeMismatch { method, position, expected, found } => write!(
+                f,
+                "argument {position} of `{method}` expected `{expected}` but found `{found}`"
+            ),
+            Self::ArgumentCountMismatch { method, expected, found } => write!(
+                f,
+                "`{method}` expected {expected} arguments but found {found}"
+            ),
+            Self::ArrayTooLong { length } => write!(
+                f,
+                "array of {length} elements is longer than a C# array can be"
+            ),
             Self::Io(err) => write!(f, "{err}"),
             Self::Json(err) => write!(f, "{err}"),
         }
@@ -2,10 +2,11 @@ use std::sync::Arc;
 
 use hostfxr_sys::{
     dlopen2::wrapper::Container, get_function_pointer_fn, hostfxr_delegate_type, hostfxr_handle,
-    load_assembly_fn, wrapper::Hostfxr as HostfxrLibrary,
+    hostfxr_initialize_parameters, load_assembly_fn, wrapper::Hostfxr as HostfxrLibrary,
 };
 
 use super::runtime::Paths;
+use crate::{Error, Result};
 
 #[cfg(target_os = "windows")]
 pub fn to_char_t(value: impl AsRef<str>) -> widestring::U16String {
@@ -18,11 +19,7 @@ pub fn to_char_t(value: impl AsRef<str>) -> widestring::U16String {
 
 #[cfg(not(target_os = "windows"))]
 fn to_char_t(value: impl AsRef<str>) -> std::ffi::CString {
-    let mut value = value.as_ref().to_string();
-    if !value.ends_with('\0') {
-        value.push('\0');
-    }
-    std::ffi::CString::from_str(&value).unwrap()
+    std::ffi::CString::new(value.as_ref().trim_end_matches('\0')).unwrap()
 }
 
 #[derive(Clone)]
@@ -35,24 +32,57 @@ unsafe impl Send for Hostfxr {}
 unsafe impl Sync for Hostfxr {}
 
 impl Hostfxr {
-    pub fn new(paths: &Paths) -> Self {
+    pub fn new(paths: &Paths) -> Result<Self> {
         log::debug!("[init] hostfxr");
 
-        let hostfxr_library = unsafe {
-            Container::<HostfxrLibrary>::load(&paths.hostfxr)
-                .expect("failed to load hostfxr and defined path")
+        if !paths.hostfxr.exists() {
+            return Err(Error::HostfxrNotFound(paths.hostfxr.clone()));
+        }
+        let hostfxr_library = unsafe { Container::<HostfxrLibrary>::load(&paths.hostfxr) }
+            .map_err(|_| Error::HostfxrNotFound(paths.hostfxr.clone()))?;
+
+        // The dotnet root is explicit so an app-local runtime is used instead of a global install
+        let host_path = to_char_t(std::env::current_exe()?.display().to_string());
+        let dotnet_root = to_char_t(paths.dotnet.display().to_string());
+        let parameters = hostfxr_initialize_parameters {
+            size: std::mem::size_of::<hostfxr_initialize_parameters>(),
+            host_path: host_path.as_ptr(),
+            dotnet_root: dotnet_root.as_ptr(),
         };
 
         let mut ctx: hostfxr_handle = std::ptr::null();
         let path = to_char_t(paths.config.display().to_string());
-        unsafe {
+        let result = unsafe {
             hostfxr_library.hostfxr_initialize_for_runtime_config(
                 path.as_ptr(),
-                std::ptr::null(),
+                &raw const parameters,
                 &raw mut ctx,
             )
         };
+        // Positive codes are successes, e.g. the host was already initialized
+        if result < 0 || ctx.is_null() {
+            return Err(Error::RuntimeConfigInvalid { code: result });
+        }
 
+        match Self::load_runtime(&hostfxr_library, ctx, paths) {
+            Ok(get_function_pointer) => Ok(Self {
+                lib: Arc::new(hostfxr_library),
+                ctx,
+                get_function_pointer,
+            }),
+            Err(err) => {
+                unsafe { hostfxr_library.hostfxr_close(ctx) };
+                Err(err)
+            }
+        }
+    }
+
+    /// Fetch the runtime delegates and load `Runtime.dll` into an initialized context
+    fn load_runtime(
+        hostfxr_library: &Container<HostfxrLibrary>,
+        ctx: hostfxr_handle,
+        paths: &Paths,
+    ) -> Result<get_function_pointer_fn> {
         let mut load_assembly: *const () = std::ptr::null();
         let result = unsafe {
             hostfxr_library.hostfxr_get_runtime_delegate(
@@ -61,10 +91,9 @@ impl Hostfxr {
                 &raw mut load_assembly,
             )
         };
-        assert!(
-            result == 0 && !load_assembly.is_null(),
-            "failed to load 'load_assembly' from hostfxr"
-        );
+        if result != 0 || load_assembly.is_null() {
+            return Err(Error::DelegateUnavailable { name: "load_assembly".into(), code: result });
+        }
         let load_assembly: load_assembly_fn = unsafe { std::mem::transmute(load_assembly) };
 
         let mut get_function_pointer: *const () = std::ptr::null();
@@ -75,23 +104,20 @@ impl Hostfxr {
                 &raw mut get_function_pointer,
             )
         };
-        assert!(
-            result == 0 && !get_function_pointer.is_null(),
-            "failed to load 'load_assembly' from hostfxr"
-        );
+        if result != 0 || get_function_pointer.is_null() {
+            return Err(Error::DelegateUnavailable { name: "get_function_pointer".into(), code: result });
+        }
         let get_function_pointer: get_function_pointer_fn =
             unsafe { std::mem::transmute(get_function_pointer) };
 
         log::debug!("[load] Runtime.dll");
         let dll = to_char_t(paths.dll.display().to_string());
         let result = unsafe { load_assembly(dll.as_ptr(), std::ptr::null(), std::ptr::null()) };
-        assert_eq!(result, 0, "failed to load dll");
-
-        Self {
-            lib: Arc::new(hostfxr_library),
-            ctx,
-            get_function_pointer,
+        if result != 0 {
+            return Err(Error::RuntimeAssemblyLoadFailed { code: result });
         }
+
+        Ok(get_function_pointer)
     }
 
     /// # Safety
@@ -101,7 +127,7 @@ impl Hostfxr {
         r#type: &str,
         method: &str,
         delegate: &str,
-    ) -> *const () {
+    ) -> Result<*const ()> {
         let type_name = to_char_t(r#type);
         let method_name = to_char_t(method);
         let delegate_type_name = to_char_t(delegate);
@@ -117,12 +143,50 @@ impl Hostfxr {
                 (&raw mut delegate).cast(),
             )
         };
-        assert_eq!(
-            result, 0,
-            "hostfxr failed to fetch dll function with delegate: 0x{:X}\n{}\n{method}",
-            result,
-            r#type,
-        );
-        delegate
+        if result != 0 || delegate.is_null() {
+            return Err(Error::DelegateUnavailable { name: format!("{}::{method}", r#type), code: result });
+        }
+        Ok(delegate)
+    }
+}
+
+/// Human readable description of a hostfxr status code or a common runtime HRESULT
+pub fn describe_status(code: i32) -> &'static str {
+    match code as u32 {
+        0x0000_0000 => "success",
+        0x0000_0001 => "success, the host was already initialized",
+        0x0000_0002 => "success, the host was already initialized with different runtime properties",
+        0x8000_8081 => "invalid argument",
+        0x8000_8082 => "failed to load hostpolicy",
+        0x8000_8083 => "hostpolicy is missing",
+        0x8000_8084 => "hostpolicy is missing a required entry point",
+        0x8000_8085 => "failed to find the current host",
+        0x8000_8087 => "failed to resolve coreclr",
+        0x8000_8088 => "failed to bind to coreclr",
+        0x8000_8089 => "failed to initialize coreclr",
+        0x8000_808A => "coreclr failed to execute the application",
+        0x8000_808B => "failed to initialize the dependency resolver",
+        0x8000_808C => "failed to resolve dependencies in the deps.json",
+        0x8000_808E => "failed to initialize the host",
+        0x8000_8091 => "invalid arguments passed to the host",
+        0x8000_8092 => "the runtimeconfig.json is missing or invalid",
+        0x8000_8095 => "the framework requested by the runtimeconfig.json is not installed",
+        0x8000_8096 => "the host api call failed",
+        0x8000_8097 => "the host api buffer is too small",
+        0x8000_809B => "the requested framework is not compatible with the one already loaded",
+        0x8000_80A1 => "a runtime property was defined more than once",
+        0x8000_80A2 => "the host api version is not supported",
+        0x8000_80A3 => "the host is in an invalid state",
+        0x8000_80A4 => "the runtime property was not found",
+        0x8000_80A5 => "the runtimeconfig.json is incompatible with the running runtime",
+        0x8000_80A6 => "the host api does not support this scenario",
+        0x8000_80A7 => "the host feature is disabled",
+        0x8007_0002 => "the file was not found",
+        0x8007_000B => "the assembly has a bad image format",
+        0x8013_1040 => "the assembly manifest does not match its reference",
+        0x8013_1513 => "the method was not found",
+        0x8013_1522 => "the type failed to load",
+        0x8013_1534 => "the type initializer threw an exception",
+        _ => "unknown status",
     }
 }
@@ -1,126 +1,261 @@
-mod config;
+use std::path::PathBuf;
+
+pub mod config;
+pub use config::Config;
+
+// Distributed games only load the packaged assemblies, the projects are generated by the build script
+#[cfg_attr(feature = "distribute", allow(dead_code))]
+mod project;
 
 mod hostfxr;
 mod error;
 pub use error::{Error, Result};
 
+pub mod reflection;
 pub mod runtime;
 use runtime::AssemblyType;
-pub use runtime::{Script, Runtime};
+pub use runtime::{FieldAccessor, ManagedArgs, ManagedStruct, ManagedType, PropertyAccessor, Script, Runtime};
+pub use bevy_cs_managed_derive::ManagedStruct;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+}
 
 pub mod dotnet;
 
-fn format_scripts_csproj(net: &str, framework: &str) -> String {
-    format!(
-        r#"<Project Sdk="Microsoft.NET.Sdk">
-  <PropertyGroup>
-    <TargetFramework>{net}</TargetFramework>
-    <RuntimeFrameworkVersion>{framework}</RuntimeFrameworkVersion>
-    <DebugType>portable</DebugType>
-    <RollForward>Disable</RollForward>
-    <ImplicitUsings>disable</ImplicitUsings>
-    <Nullable>enable</Nullable>
-  </PropertyGroup>
-  <ItemGroup>
-    <FrameworkReference Update="Microsoft.NETCore.App" RuntimeFrameworkVersion="{framework}" />
-  </ItemGroup>
-  <ItemGroup Condition="'$(Configuration)' == 'Debug'">
-    <ProjectReference Include="..\engine\Engine.csproj" />
-  </ItemGroup>
-  <ItemGroup Condition="'$(Configuration)' != 'Debug'">
-    <Reference Include="Engine">
-      <HintPath>..\engine\bin\Release\{net}\Engine.dll</HintPath>
-    </Reference>
-  </ItemGroup>
-</Project>"#
-    )
+/// Inserted instead of [`Runtime`] when an [optional](CSharpPlugin::optional) plugin fails to start
+#[derive(bevy::ecs::resource::Resource, Debug)]
+pub struct ScriptingDisabled {
+    pub error: Error,
 }
 
-fn format_engine_csproj(net: &str, framework: &str) -> String {
-    format!(
-        r#"<Project Sdk="Microsoft.NET.Sdk">
-  <PropertyGroup>
-    <TargetFramework>{net}</TargetFramework>
-    <RuntimeFrameworkVersion>{framework}</RuntimeFrameworkVersion>
-    <ImplicitUsings>disable</ImplicitUsings>
-    <DebugType>portable</DebugType>
-    <Nullable>enable</Nullable>
-    <RollForward>Disable</RollForward>
-  </PropertyGroup>
-  <ItemGroup>
-    <FrameworkReference Update="Microsoft.NETCore.App" RuntimeFrameworkVersion="{framework}" />
-  </ItemGroup>
-</Project>"#
-    )
+/// How the `Engine` and `Scripts` assemblies are produced when the plugin is built
+///
+/// With the `distribute` feature the assemblies are always [prebuilt](BuildMode::Prebuilt)
+/// by the build script and this setting is ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildMode {
+    /// Generate the projects and compile them with `dotnet build -c Debug`
+    Debug,
+    /// Generate the projects and compile them with `dotnet build -c Release`
+    Release,
+    /// Compile the sources in-process with Roslyn, see [`Runtime::compile`]
+    InProcess,
+    /// Skip compilation and load the assemblies already in the output directory
+    Prebuilt,
 }
 
-pub struct CSharpPlugin;
-impl bevy::app::Plugin for CSharpPlugin {
-    fn build(&self, app: &mut bevy::app::App) {
-        let mut runtime = Runtime::new().unwrap();
+impl BuildMode {
+    /// Configuration passed to `dotnet build -c`
+    ///
+    /// Modes that don't build the projects use `Release`, which prebuilt assemblies are packaged with.
+    pub fn as_configuration(&self) -> &'static str {
+        match self {
+            Self::Debug => "Debug",
+            Self::Release | Self::InProcess | Self::Prebuilt => "Release",
+        }
+    }
+}
 
-        assert!(runtime.library.ping(), "failed to bind and initialize C# Runtime");
+impl Default for BuildMode {
+    /// [`BuildMode::Release`] for debug builds, [`BuildMode::Prebuilt`] for release builds
+    /// and with the `distribute` feature
+    fn default() -> Self {
+        if cfg!(debug_assertions) && !cfg!(feature = "distribute") {
+            Self::Release
+        } else {
+            Self::Prebuilt
+        }
+    }
+}
+
+/// C# type name and source of a struct shared with C#
+type SharedStruct = (&'static str, fn() -> String);
+
+/// Hosts the .NET runtime and loads the `Engine` and `Scripts` assemblies
+///
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_cs_managed::{BuildMode, CSharpPlugin};
+/// App::new().add_plugins(
+///     CSharpPlugin::default()
+///         .scripts_dir("game/scripts")
+///         .engine_dir("game/engine")
+///         .auto_register(false)
+///         .build_mode(BuildMode::Debug),
+/// );
+/// ```
+pub struct CSharpPlugin {
+    config: PathBuf,
+    scripts_dir: Option<PathBuf>,
+    engine_dir: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+    auto_register: bool,
+    build_mode: BuildMode,
+    optional: bool,
+    #[cfg_attr(feature = "distribute", allow(dead_code))]
+    structs: Vec<SharedStruct>,
+}
+
+impl Default for CSharpPlugin {
+    fn default() -> Self {
+        Self {
+            config: PathBuf::from("managed.config.json"),
+            scripts_dir: None,
+            engine_dir: None,
+            output_dir: None,
+            auto_register: true,
+            build_mode: BuildMode::default(),
+            optional: false,
+            structs: Vec::new(),
+        }
+    }
+}
+
+impl CSharpPlugin {
+    /// Path of the managed config, `managed.config.json` by default
+    pub fn config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config = path.into();
+        self
+    }
+
+    /// Override the script roots from the managed config with a single directory
+    pub fn scripts_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.scripts_dir = Some(dir.into());
+        self
+    }
+
+    /// Override the engine roots from the managed config with a single directory
+    pub fn engine_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.engine_dir = Some(dir.into());
+        self
+    }
+
+    /// Override the directory, relative to the executable, managed assemblies are placed in
+    pub fn output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    /// Register every script class with [`Runtime::register_all`], enabled by default
+    pub fn auto_register(mut self, enabled: bool) -> Self {
+        self.auto_register = enabled;
+        self
+    }
+
+    pub fn build_mode(mut self, mode: BuildMode) -> Self {
+        self.build_mode = mode;
+        self
+    }
+
+    /// Write the C# struct of a [`ManagedStruct`](derive@ManagedStruct) into the engine project
+    ///
+    /// The struct is written to the `Generated` directory of the first engine root before the
+    /// assemblies are compiled, it is committed alongside the engine sources so distribute builds
+    /// use it too.
+    pub fn managed_struct<T: runtime::ManagedStruct>(mut self) -> Self {
+        self.structs.push((T::TYPE_NAME, T::csharp_source));
+        self
+    }
+
+    /// Insert [`ScriptingDisabled`] and keep the app running when the runtime fails to start,
+    /// instead of panicking
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+
+    fn start(&self) -> Result<Runtime> {
+        let mut runtime = Runtime::new(self.load_config()?)?;
+
+        if !runtime.library.ping() {
+            return Err(Error::UnknownManaged);
+        }
         runtime.scope = Some(runtime.library.create_scope());
 
-        #[cfg(debug_assertions)]
-        {
-            if !runtime.get_managed_path().exists() {
-                std::fs::create_dir_all(runtime.get_managed_path()).unwrap();
-            }
+        #[cfg(not(feature = "distribute"))]
+        if self.build_mode != BuildMode::Prebuilt {
+            project::write_generated(
+                &runtime.config.engine,
+                self.structs.iter().map(|(name, source)| (*name, source())),
+            )?;
+        }
 
-            let engine_path = runtime.get_managed_path().join("engine");
-            if !engine_path.exists() {
-                std::fs::create_dir_all(&engine_path).unwrap();
-            }
-            std::fs::write(
-                engine_path.join("Engine.csproj"),
-                format_engine_csproj(runtime.get_net_version(), runtime.get_framework_version()),
-            )
-            .unwrap();
-
-            let scripts_path = runtime.get_managed_path().join("scripts");
-            if !scripts_path.exists() {
-                std::fs::create_dir_all(&engine_path).unwrap();
-            }
-            std::fs::write(
-                scripts_path.join("Scripts.csproj"),
-                format_scripts_csproj(runtime.get_net_version(), runtime.get_framework_version()),
-            )
-            .unwrap();
+        #[cfg(not(feature = "distribute"))]
+        if let BuildMode::Debug | BuildMode::Release = self.build_mode {
+            let (net, framework) = (runtime.get_net_version(), runtime.get_framework_version());
+            project::generate_projects(&runtime.config, net, framework)?;
+            project::generate_ide_files(&runtime.config, net, framework)?;
 
-            let builder = dotnet::Builder::new(runtime.get_dotnet_path(), runtime.get_net_version());
+            let builder = dotnet::Builder::new(runtime.get_dotnet_path(), runtime.get_net_version())
+                .with_configuration(self.build_mode.as_configuration())
+                .with_sources(&runtime.config.nuget.sources)
+                .with_packages(runtime.config.nuget.packages.as_ref());
 
-            if !runtime.paths.exe.join("managed").exists() {
-                std::fs::create_dir_all(runtime.paths.exe.join("managed")).unwrap();
-            }
+            std::fs::create_dir_all(runtime.get_output_path())?;
 
-            let (name, base) = builder.build(engine_path.join("Engine.csproj")).unwrap();
+            let (name, base) = builder.build(runtime.get_engine_path().join("Engine.csproj"))?;
             std::fs::copy(
                 base.join(format!("{name}.dll")),
-                AssemblyType::Engine.path(&runtime.paths.exe),
-            )
-            .unwrap();
+                AssemblyType::Engine.path(runtime.get_output_path()),
+            )?;
+            project::copy_dependencies(&base, runtime.get_output_path())?;
 
-            let (name, base) = builder.build(scripts_path.join("Scripts.csproj")).unwrap();
+            let (name, base) = builder.build(runtime.get_scripts_path().join("Scripts.csproj"))?;
             std::fs::copy(
                 base.join(format!("{name}.dll")),
-                AssemblyType::Scripts.path(&runtime.paths.exe),
-            )
-            .unwrap();
+                AssemblyType::Scripts.path(runtime.get_output_path()),
+            )?;
+            project::copy_dependencies(&base, runtime.get_output_path())?;
         }
 
-        runtime.load(AssemblyType::Engine).unwrap();
-        runtime.load(AssemblyType::Scripts).unwrap();
+        runtime.load_dependencies()?;
+        // Distributed games only load the assemblies packaged at build time
+        if self.build_mode == BuildMode::InProcess && !cfg!(feature = "distribute") {
+            let engine = project::sources(&runtime.config.engine);
+            runtime.compile(AssemblyType::Engine, engine)?;
+            let scripts = project::sources(&runtime.config.scripts);
+            runtime.compile(AssemblyType::Scripts, scripts)?;
+        } else {
+            runtime.load(AssemblyType::Engine)?;
+            runtime.load(AssemblyType::Scripts)?;
+        }
+
+        if self.auto_register {
+            runtime.register_all()?;
+        }
 
-        for entry in glob::glob("assets/scripts/**/*.cs").unwrap() {
-            match entry {
-                Ok(path) => if !path.iter().any(|c| c.to_string_lossy() == runtime.get_net_version()) {
-                    runtime.register(path.file_stem().unwrap().to_string_lossy()).unwrap();
-                },
-                Err(e) => eprintln!("{:?}", e),
-            } 
+        Ok(runtime)
+    }
+
+    fn load_config(&self) -> Result<Config> {
+        let mut config = Config::load(&self.config)?;
+        if let Some(dir) = &self.scripts_dir {
+            config.scripts.roots = vec![dir.clone()];
+        }
+        if let Some(dir) = &self.engine_dir {
+            config.engine.roots = vec![dir.clone()];
+        }
+        if let Some(dir) = &self.output_dir {
+            config.output = dir.clone();
         }
+        Ok(config)
+    }
+}
 
-        app.insert_resource(runtime);
+impl bevy::app::Plugin for CSharpPlugin {
+    fn build(&self, app: &mut bevy::app::App) {
+        match self.start() {
+            Ok(runtime) => {
+                app.insert_resource(runtime);
+            }
+            Err(error) if self.optional => {
+                log::error!("C# scripting disabled: {error}");
+                app.insert_resource(ScriptingDisabled { error });
+            }
+            Err(error) => panic!("failed to start the C# runtime: {error}"),
+        }
     }
 }
@@ -0,0 +1,525 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::{Config, Project, RollForward};
+
+/// Project type of SDK style C# projects in a solution file
+const CSHARP_PROJECT_TYPE: &str = "9A19103F-16F7-4668-BE54-9A1E7A4F7556";
+
+/// Write the `Engine` and `Scripts` project files
+pub fn generate_projects(config: &Config, net: &str, framework: &str) -> std::io::Result<()> {
+    let engine_path = config.engine.root();
+    std::fs::create_dir_all(engine_path)?;
+    std::fs::write(
+        engine_path.join("Engine.csproj"),
+        format_engine_csproj(&config.engine, net, framework, config.roll_forward),
+    )?;
+
+    let scripts_path = config.scripts.root();
+    std::fs::create_dir_all(scripts_path)?;
+    std::fs::write(
+        scripts_path.join("Scripts.csproj"),
+        format_scripts_csproj(&config.scripts, &config.engine, net, framework, config.roll_forward),
+    )?;
+
+    Ok(())
+}
+
+/// Write a solution and IDE files tying the generated projects and any user projects together
+pub fn generate_ide_files(config: &Config, net: &str, framework: &str) -> std::io::Result<()> {
+    let engine_path = config.engine.root();
+    let scripts_path = config.scripts.root();
+
+    let properties = scripts_path.join("Properties");
+    std::fs::create_dir_all(&properties)?;
+    std::fs::write(
+        properties.join("launchSettings.json"),
+        format_launch_settings(&std::env::current_exe()?, &std::env::current_dir()?),
+    )?;
+
+    let solution = &config.solution;
+    std::fs::create_dir_all(solution)?;
+
+    let projects = [engine_path.join("Engine.csproj"), scripts_path.join("Scripts.csproj")]
+        .into_iter()
+        .chain(config.engine.references.iter().cloned())
+        .chain(config.scripts.references.iter().cloned())
+        .collect::<Vec<_>>();
+    std::fs::write(solution.join("Managed.sln"), format_solution(solution, &projects))?;
+    std::fs::write(
+        solution.join("Directory.Build.props"),
+        format_directory_build_props(net, framework, config.roll_forward),
+    )?;
+
+    // Only written once so it can be customized
+    let editorconfig = solution.join(".editorconfig");
+    if !editorconfig.exists() {
+        std::fs::write(editorconfig, EDITORCONFIG)?;
+    }
+
+    Ok(())
+}
+
+/// Directory in the engine project generated C# sources are written to
+pub const GENERATED: &str = "Generated";
+
+/// Write generated C# types, named by their full name, into the engine project
+///
+/// Files are only written when their content changes so they don't trigger a rebuild.
+pub fn write_generated(
+    engine: &Project,
+    sources: impl IntoIterator<Item = (&'static str, String)>,
+) -> std::io::Result<()> {
+    let generated = engine.root().join(GENERATED);
+    for (name, source) in sources {
+        let path = generated.join(format!("{name}.g.cs"));
+        if std::fs::read_to_string(&path).ok().as_deref() != Some(source.as_str()) {
+            log::debug!("[write] {}", path.display());
+            std::fs::create_dir_all(&generated)?;
+            std::fs::write(&path, source)?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy the dependencies, like NuGet packages, from a project's build output next to the managed assemblies
+pub fn copy_dependencies(base: &Path, output: &Path) -> std::io::Result<()> {
+    for entry in base.read_dir()? {
+        let path = entry?.path();
+        let is_dependency = path.extension().is_some_and(|ext| ext == "dll")
+            && !path
+                .file_stem()
+                .is_some_and(|stem| stem == "Engine" || stem == "Scripts");
+
+        if is_dependency {
+            std::fs::copy(&path, output.join(path.file_name().unwrap()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Express `path` relative to the directory `base`
+///
+/// Paths on another drive or share can't be made relative and are returned absolute.
+pub fn relative_to(path: impl AsRef<Path>, base: impl AsRef<Path>) -> PathBuf {
+    let path = normalize(path.as_ref());
+    let base = normalize(base.as_ref());
+
+    if path.components().next() != base.components().next() {
+        return path;
+    }
+
+    let common = path
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut out = PathBuf::new();
+    for _ in base.components().skip(common) {
+        out.push(Component::ParentDir);
+    }
+    for component in path.components().skip(common) {
+        out.push(component);
+    }
+    out
+}
+
+/// Absolute path with `.` and `..` resolved lexically, so `../game` and `assets/../game` compare equal
+fn normalize(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or(path.to_path_buf());
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            component => out.push(component),
+        }
+    }
+    out
+}
+
+fn format_properties(project: &Project) -> String {
+    let mut out = format!("    <Nullable>{}</Nullable>\n", project.nullable);
+    if let Some(lang_version) = &project.lang_version {
+        out.push_str(&format!("    <LangVersion>{lang_version}</LangVersion>\n"));
+    }
+    if !project.defines.is_empty() {
+        out.push_str(&format!(
+            "    <DefineConstants>$(DefineConstants);{}</DefineConstants>\n",
+            project.defines.join(";")
+        ));
+    }
+    if !project.packages.is_empty() {
+        // Class libraries don't copy package assemblies to their output by default
+        out.push_str("    <CopyLocalLockFileAssemblies>true</CopyLocalLockFileAssemblies>\n");
+    }
+    out
+}
+
+fn format_items(project: &Project) -> String {
+    let base = project.root();
+    let mut out = String::new();
+
+    let sources = project
+        .roots
+        .iter()
+        .skip(1)
+        .map(|root| relative_to(root, base))
+        .map(|root| {
+            format!(
+                "    <Compile Include=\"{0}/**/*.cs\" Exclude=\"{0}/bin/**;{0}/obj/**\" />\n",
+                root.display()
+            )
+        })
+        .collect::<String>();
+    if !sources.is_empty() {
+        out.push_str(&format!("  <ItemGroup>\n{sources}  </ItemGroup>\n"));
+    }
+
+    let references = project
+        .references
+        .iter()
+        .map(|reference| {
+            format!(
+                "    <ProjectReference Include=\"{}\" />\n",
+                relative_to(reference, base).display()
+            )
+        })
+        .collect::<String>();
+    if !references.is_empty() {
+        out.push_str(&format!("  <ItemGroup>\n{references}  </ItemGroup>\n"));
+    }
+
+    let packages = project
+        .packages
+        .iter()
+        .map(|package| {
+            format!(
+                "    <PackageReference Include=\"{}\" Version=\"{}\" />\n",
+                package.name, package.version
+            )
+        })
+        .collect::<String>();
+    if !packages.is_empty() {
+        out.push_str(&format!("  <ItemGroup>\n{packages}  </ItemGroup>\n"));
+    }
+
+    out
+}
+
+pub fn format_scripts_csproj(
+    project: &Project,
+    engine: &Project,
+    net: &str,
+    framework: &str,
+    roll_forward: RollForward,
+) -> String {
+    let roll_forward = roll_forward.as_runtime_config();
+    let properties = format_properties(project);
+    let items = format_items(project);
+    let engine_dir = relative_to(engine.root(), project.root());
+    let engine_dir = engine_dir.display();
+    format!(
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>{net}</TargetFramework>
+    <RuntimeFrameworkVersion>{framework}</RuntimeFrameworkVersion>
+    <DebugType>portable</DebugType>
+    <RollForward>{roll_forward}</RollForward>
+    <ImplicitUsings>disable</ImplicitUsings>
+{properties}  </PropertyGroup>
+  <ItemGroup>
+    <FrameworkReference Update="Microsoft.NETCore.App" RuntimeFrameworkVersion="{framework}" />
+  </ItemGroup>
+  <ItemGroup Condition="'$(Configuration)' == 'Debug'">
+    <ProjectReference Include="{engine_dir}/Engine.csproj" />
+  </ItemGroup>
+  <ItemGroup Condition="'$(Configuration)' != 'Debug'">
+    <Reference Include="Engine">
+      <HintPath>{engine_dir}/bin/Release/{net}/Engine.dll</HintPath>
+    </Reference>
+  </ItemGroup>
+{items}</Project>"#
+    )
+}
+
+pub fn format_engine_csproj(project: &Project, net: &str, framework: &str, roll_forward: RollForward) -> String {
+    let roll_forward = roll_forward.as_runtime_config();
+    let properties = format_properties(project);
+    let items = format_items(project);
+    format!(
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <TargetFramework>{net}</TargetFramework>
+    <RuntimeFrameworkVersion>{framework}</RuntimeFrameworkVersion>
+    <ImplicitUsings>disable</ImplicitUsings>
+    <DebugType>portable</DebugType>
+    <RollForward>{roll_forward}</RollForward>
+{properties}  </PropertyGroup>
+  <ItemGroup>
+    <FrameworkReference Update="Microsoft.NETCore.App" RuntimeFrameworkVersion="{framework}" />
+  </ItemGroup>
+{items}</Project>"#
+    )
+}
+
+/// All `.cs` files under the project's roots, excluding build output
+pub fn sources(project: &Project) -> Vec<PathBuf> {
+    let mut sources = Vec::new();
+    for root in &project.roots {
+        for entry in glob::glob(&format!("{}/**/*.cs", root.display())).unwrap() {
+            match entry {
+                Ok(path) => {
+                    let is_output = path
+                        .strip_prefix(root)
+                        .unwrap_or(&path)
+                        .iter()
+                        .any(|c| c == "bin" || c == "obj");
+                    if !is_output {
+                        sources.push(path);
+                    }
+                }
+                Err(e) => log::error!("{e}"),
+            }
+        }
+    }
+    sources
+}
+
+/// Stable project GUID derived from the project path so regenerating the solution doesn't change it
+fn project_guid(path: &Path) -> String {
+    let path = path.display().to_string();
+    let hash = |seed: u64| {
+        path.bytes()
+            .fold(seed, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3))
+    };
+    let (a, b) = (hash(0xcbf29ce484222325), hash(0x84222325cbf29ce4));
+    format!(
+        "{:08X}-{:04X}-{:04X}-{:04X}-{:012X}",
+        a >> 32,
+        (a >> 16) & 0xFFFF,
+        a & 0xFFFF,
+        b >> 48,
+        b & 0xFFFF_FFFF_FFFF
+    )
+}
+
+pub fn format_solution(base: &Path, projects: &[PathBuf]) -> String {
+    let projects = projects
+        .iter()
+        .map(|path| {
+            (
+                path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+                relative_to(path, base).display().to_string().replace('/', "\\"),
+                project_guid(path),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let declarations = projects
+        .iter()
+        .map(|(name, path, guid)| {
+            format!("Project(\"{{{CSHARP_PROJECT_TYPE}}}\") = \"{name}\", \"{path}\", \"{{{guid}}}\"\r\nEndProject\r\n")
+        })
+        .collect::<String>();
+
+    let configurations = projects
+        .iter()
+        .flat_map(|(_, _, guid)| {
+            ["Debug", "Release"].map(|config| {
+                format!(
+                    "\t\t{{{guid}}}.{config}|Any CPU.ActiveCfg = {config}|Any CPU\r\n\t\t{{{guid}}}.{config}|Any CPU.Build.0 = {config}|Any CPU\r\n"
+                )
+            })
+        })
+        .collect::<String>();
+
+    format!(
+        "\r\nMicrosoft Visual Studio Solution File, Format Version 12.00\r\n\
+         # Visual Studio Version 17\r\n\
+         VisualStudioVersion = 17.0.31903.59\r\n\
+         MinimumVisualStudioVersion = 10.0.40219.1\r\n\
+         {declarations}\
+         Global\r\n\
+         \tGlobalSection(SolutionConfigurationPlatforms) = preSolution\r\n\
+         \t\tDebug|Any CPU = Debug|Any CPU\r\n\
+         \t\tRelease|Any CPU = Release|Any CPU\r\n\
+         \tEndGlobalSection\r\n\
+         \tGlobalSection(ProjectConfigurationPlatforms) = postSolution\r\n\
+         {configurations}\
+         \tEndGlobalSection\r\n\
+         EndGlobal\r\n"
+    )
+}
+
+/// Settings shared by every project under the solution directory, including user projects
+pub fn format_directory_build_props(net: &str, framework: &str, roll_forward: RollForward) -> String {
+    let roll_forward = roll_forward.as_runtime_config();
+    format!(
+        r#"<Project>
+  <PropertyGroup>
+    <TargetFramework>{net}</TargetFramework>
+    <RuntimeFrameworkVersion>{framework}</RuntimeFrameworkVersion>
+    <RollForward>{roll_forward}</RollForward>
+    <ImplicitUsings>disable</ImplicitUsings>
+    <DebugType>portable</DebugType>
+  </PropertyGroup>
+</Project>"#
+    )
+}
+
+/// Launch profile that starts the game when debugging the scripts from an IDE
+pub fn format_launch_settings(exe: &Path, cwd: &Path) -> String {
+    serde_json::to_string_pretty(&serde_json::json!({
+        "profiles": {
+            "Game": {
+                "commandName": "Executable",
+                "executablePath": exe.display().to_string(),
+                "workingDirectory": cwd.display().to_string(),
+            }
+        }
+    }))
+    .unwrap()
+}
+
+const EDITORCONFIG: &str = r#"root = true
+
+[*]
+charset = utf-8
+end_of_line = lf
+insert_final_newline = true
+trim_trailing_whitespace = true
+
+[*.cs]
+indent_style = space
+indent_size = 4
+
+[*.{csproj,props,json}]
+indent_style = space
+indent_size = 2
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Empty directory under the system temp directory, removed when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("bevy_cs_managed-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn solution_paths(solution: &str) -> Vec<String> {
+        solution
+            .lines()
+            .filter(|line| line.starts_with("Project("))
+            .map(|line| line.split(", ").nth(1).unwrap().trim_matches('"').to_string())
+            .collect()
+    }
+
+    #[test]
+    fn relative_to_sibling_and_nested_directories() {
+        assert_eq!(relative_to("assets/engine/Engine.csproj", "assets"), Path::new("engine/Engine.csproj"));
+        assert_eq!(relative_to("game/scripts", "assets"), Path::new("../game/scripts"));
+        assert_eq!(relative_to("assets", "assets"), Path::new(""));
+        assert_eq!(relative_to("assets", "assets/engine/src"), Path::new("../.."));
+    }
+
+    #[test]
+    fn relative_to_resolves_parent_directories() {
+        let cwd = std::env::current_dir().unwrap();
+        let name = cwd.file_name().unwrap();
+
+        assert_eq!(relative_to("assets/../game/scripts", "assets"), Path::new("../game/scripts"));
+        assert_eq!(
+            relative_to("assets/engine", "../solution"),
+            Path::new("..").join(name).join("assets/engine")
+        );
+        assert_eq!(relative_to("../shared", "assets"), Path::new("../../shared"));
+    }
+
+    #[test]
+    fn solution_with_default_layout() {
+        let projects = [
+            PathBuf::from("assets/engine/Engine.csproj"),
+            PathBuf::from("assets/scripts/Scripts.csproj"),
+        ];
+        let solution = format_solution(Path::new("assets"), &projects);
+        assert_eq!(
+            solution_paths(&solution),
+            ["engine\\Engine.csproj", "scripts\\Scripts.csproj"]
+        );
+    }
+
+    #[test]
+    fn solution_with_scripts_outside_the_solution_directory() {
+        let projects = [
+            PathBuf::from("assets/engine/Engine.csproj"),
+            PathBuf::from("game/scripts/Scripts.csproj"),
+            PathBuf::from("../tools/Tools.csproj"),
+        ];
+        let solution = format_solution(Path::new("assets"), &projects);
+        assert_eq!(
+            solution_paths(&solution),
+            [
+                "engine\\Engine.csproj",
+                "..\\game\\scripts\\Scripts.csproj",
+                "..\\..\\tools\\Tools.csproj"
+            ]
+        );
+    }
+
+    #[test]
+    fn project_guids_are_stable_and_unique() {
+        let engine = Path::new("assets/engine/Engine.csproj");
+        let scripts = Path::new("assets/scripts/Scripts.csproj");
+        assert_eq!(project_guid(engine), project_guid(engine));
+        assert_ne!(project_guid(engine), project_guid(scripts));
+    }
+
+    #[test]
+    fn ide_files_with_a_custom_scripts_dir() {
+        let dir = TempDir::new("ide-files");
+        let config = Config {
+            solution: dir.0.join("assets"),
+            engine: Project::with_root(dir.0.join("assets/engine")),
+            scripts: Project::with_root(dir.0.join("game/scripts")),
+            ..Default::default()
+        };
+
+        generate_ide_files(&config, "net8.0", "8.0.0").unwrap();
+
+        let solution = std::fs::read_to_string(dir.0.join("assets/Managed.sln")).unwrap();
+        assert_eq!(
+            solution_paths(&solution),
+            ["engine\\Engine.csproj", "..\\game\\scripts\\Scripts.csproj"]
+        );
+        assert!(dir.0.join("assets/Directory.Build.props").exists());
+        assert!(dir.0.join("assets/.editorconfig").exists());
+        assert!(dir.0.join("game/scripts/Properties/launchSettings.json").exists());
+    }
+
+    #[test]
+    fn scripts_project_references_an_engine_outside_its_directory() {
+        let engine = Project::with_root("assets/engine");
+        let scripts = Project::with_root("game/scripts");
+        let csproj = format_scripts_csproj(&scripts, &engine, "net8.0", "8.0.0", RollForward::Patch);
+        assert!(csproj.contains(r#"<ProjectReference Include="../../assets/engine/Engine.csproj" />"#));
+        assert!(csproj.contains("<HintPath>../../assets/engine/bin/Release/net8.0/Engine.dll</HintPath>"));
+        assert!(csproj.contains("<RollForward>LatestPatch</RollForward>"));
+    }
+}
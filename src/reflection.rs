@@ -0,0 +1,130 @@
+use serde::Deserialize;
+
+/// Description of a managed type produced by reflection in the hosted runtime
+///
+/// Type names are written the way C# would, e.g. `System.Collections.Generic.List<System.Int32>`,
+/// with nested types separated by `+`.
+#[derive(Default, Debug, Clone, Deserialize)]
+#[serde(rename_all="PascalCase")]
+pub struct TypeInfo {
+    pub full_name: String,
+    pub name: String,
+    pub namespace: Option<String>,
+    pub base_type: Option<String>,
+    pub interfaces: Vec<String>,
+    pub is_abstract: bool,
+    pub is_interface: bool,
+    pub is_value_type: bool,
+    pub is_enum: bool,
+    /// Names of the generic parameters of a generic type definition, e.g. `T`
+    pub generic_parameters: Vec<String>,
+    /// Fields declared by this type, inherited fields are described by the base type
+    pub fields: Vec<FieldInfo>,
+    /// Properties declared by this type, inherited properties are described by the base type
+    pub properties: Vec<PropertyInfo>,
+    /// Instance constructors, named `.ctor`
+    pub constructors: Vec<MethodInfo>,
+    /// Methods declared by this type, excluding property accessors and operators
+    pub methods: Vec<MethodInfo>,
+    pub attributes: Vec<AttributeInfo>,
+}
+
+impl TypeInfo {
+    pub fn field(&self, name: &str) -> Option<&FieldInfo> {
+        self.fields.iter().find(|v| v.name == name)
+    }
+
+    pub fn property(&self, name: &str) -> Option<&PropertyInfo> {
+        self.properties.iter().find(|v| v.name == name)
+    }
+
+    /// Every overload of the method `name`
+    pub fn methods<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'s MethodInfo> {
+        self.methods.iter().filter(move |v| v.name == name)
+    }
+
+    pub fn has_attribute(&self, r#type: &str) -> bool {
+        self.attributes.iter().any(|v| v.r#type == r#type)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all="PascalCase")]
+pub struct FieldInfo {
+    pub name: String,
+    pub r#type: String,
+    pub is_static: bool,
+    pub is_public: bool,
+    /// `readonly` or `const`
+    pub is_read_only: bool,
+    pub attributes: Vec<AttributeInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all="PascalCase")]
+pub struct PropertyInfo {
+    pub name: String,
+    pub r#type: String,
+    pub is_static: bool,
+    pub can_read: bool,
+    pub can_write: bool,
+    pub attributes: Vec<AttributeInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all="PascalCase")]
+pub struct MethodInfo {
+    pub name: String,
+    pub return_type: String,
+    pub is_static: bool,
+    pub is_public: bool,
+    pub is_abstract: bool,
+    pub is_virtual: bool,
+    /// Names of the generic parameters of a generic method definition, e.g. `T`
+    pub generic_parameters: Vec<String>,
+    pub parameters: Vec<ParameterInfo>,
+    pub attributes: Vec<AttributeInfo>,
+}
+
+impl MethodInfo {
+    /// C# style signature, e.g. `System.Void Update(System.Single dt)`
+    pub fn signature(&self) -> String {
+        format!(
+            "{} {}({})",
+            self.return_type,
+            self.name,
+            self.parameters
+                .iter()
+                .map(|v| match &v.name {
+                    Some(name) => format!("{} {name}", v.r#type),
+                    None => v.r#type.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all="PascalCase")]
+pub struct ParameterInfo {
+    pub name: Option<String>,
+    pub r#type: String,
+    pub is_value_type: bool,
+    /// Marshalled size of structs, `0` for primitives, enums and reference types
+    pub size: usize,
+    /// Marshalled size of the structs in an array, `0` for other types and arrays of primitives or enums
+    pub element_size: usize,
+    /// A ref struct like `Span<T>`, which can't be passed from rust
+    pub is_by_ref_like: bool,
+    pub is_out: bool,
+    pub is_optional: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all="PascalCase")]
+pub struct AttributeInfo {
+    pub r#type: String,
+    /// Constructor arguments formatted as strings, `None` for `null`
+    pub arguments: Vec<Option<String>>,
+}
@@ -1,28 +1,49 @@
 use std::{
+    any::Any,
     borrow::Cow,
     cell::RefCell,
     collections::{hash_map::Entry, HashMap},
-    ffi::{c_void, CStr},
+    ffi::{c_void, CStr, CString},
     ops::Deref,
     path::{Path, PathBuf},
     rc::Rc
 };
 
 use bevy::ecs::resource::Resource;
+use bytemuck::Pod;
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::Value;
 
-use crate::{dotnet, hostfxr::Hostfxr, Error, Result};
+use crate::{
+    config::{Config, Project, RollForward},
+    dotnet::{
+        diagnostic::Diagnostic,
+        discovery::{self, SemVer},
+    },
+    hostfxr::Hostfxr,
+    reflection::{MethodInfo, ParameterInfo, TypeInfo},
+    Error, Result,
+};
 
 include!(concat!(std::env!("OUT_DIR"), "/constants.rs"));
 
+/// Full name of the `Engine` base class scripts derive from
+pub const BEHAVIOUR: &str = "Engine.Behaviour";
+/// Full name of the `Engine` attribute marking a class as a script
+pub const SCRIPT_ATTRIBUTE: &str = "Engine.ScriptAttribute";
+
 pub struct Paths {
     pub exe: PathBuf,
     pub config: PathBuf,
     pub dll: PathBuf,
     pub dotnet: PathBuf,
     pub hostfxr: PathBuf,
-    pub managed: PathBuf,
+    /// Directory the `Engine` project is generated in
+    pub engine: PathBuf,
+    /// Directory the `Scripts` project is generated in
+    pub scripts: PathBuf,
+    /// Directory compiled managed assemblies are loaded from
+    pub output: PathBuf,
 }
 
 pub struct Versions {
@@ -45,7 +66,7 @@ impl std::fmt::Display for AssemblyType {
 }
 impl AssemblyType {
     pub fn path(&self, base: impl AsRef<Path>) -> PathBuf {
-        base.as_ref().join("managed").join(format!("{self}.dll"))
+        base.as_ref().join(format!("{self}.dll"))
     }
 }
 
@@ -54,24 +75,54 @@ pub struct Type {
     pub(crate) name: Cow<'static, str>,
     pub(crate) class: Class,
 
-    pub(crate) methods: RefCell<HashMap<(String, i32), Rc<Method>>>,
+    pub(crate) methods: RefCell<HashMap<(String, Signature), Rc<Method>>>,
+    pub(crate) fields: RefCell<HashMap<String, Rc<Accessor>>>,
+    pub(crate) properties: RefCell<HashMap<String, Rc<Accessor>>>,
     pub(crate) metadata: MetaData,
 }
 
+/// How a method overload is selected, part of the key methods are cached by
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Signature {
+    /// The first method with this many parameters
+    Arity(i32),
+    /// The method with exactly these parameter types
+    Parameters(Vec<String>),
+}
+
 pub struct Invokable<'s> {
-    instance: &'s Object,
+    /// `None` for static methods
+    instance: Option<&'s Object>,
     method: Rc<Method>,
     invoke: Invoke,
+    reflection: Invoke,
 }
 impl<'s> Invokable<'s> {
+    /// Call the method through a thunk compiled when it was resolved, arguments are read without boxing
+    ///
+    /// Methods with `ref`, `out` or pointer parameters are invoked with reflection instead. Methods taking
+    /// ref structs like `Span<T>` or `ReadOnlySpan<T>` can't be invoked and fail with
+    /// [`Error::ArgumentTypeMismatch`], take a `T[]` to receive a slice.
     pub fn invoke(&self, args: impl ManagedParams) -> Result<()> {
-        let params = args.into_managed_params();
+        self.call(self.invoke, args)
+    }
+
+    /// Call the method with `MethodInfo.Invoke`, boxing every argument
+    ///
+    /// Slower than [`Invokable::invoke`], kept to benchmark the compiled thunks against.
+    pub fn invoke_with_reflection(&self, args: impl ManagedParams) -> Result<()> {
+        self.call(self.reflection, args)
+    }
+
+    fn call(&self, invoke: Invoke, args: impl ManagedParams) -> Result<()> {
+        let params = args.into_managed_params()?;
+        self.method.check(&params)?;
         let mut err: i32 = -1;
         unsafe {
-            (self.invoke)(
+            invoke(
                 self.method.as_ptr(),
-                self.instance.as_ptr(),
-                params.as_ptr(),
+                self.instance.map(|v| v.as_ptr()).unwrap_or(std::ptr::null()),
+                params.as_slice().as_ptr(),
                 &raw mut err,
             )
         };
@@ -80,39 +131,377 @@ impl<'s> Invokable<'s> {
     }
 }
 
+/// What a marshalled argument carries, checked against the parameter it is passed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgumentKind {
+    /// A pointer to a value of this C# type and size
+    Value { type_name: &'static str, size: usize },
+    String,
+    /// A `T[]` of this element type and size
+    Array { type_name: &'static str, size: usize },
+    /// A handle to a managed object
+    Object,
+    Null,
+}
+impl ArgumentKind {
+    pub fn accepts(&self, parameter: &ParameterInfo) -> bool {
+        let r#type = parameter.r#type.strip_suffix('&').unwrap_or(&parameter.r#type);
+        let nullable = r#type
+            .strip_prefix("System.Nullable<")
+            .and_then(|v| v.strip_suffix('>'));
+
+        // Spans and other ref structs can't be boxed or read by the compiled thunks
+        if parameter.is_by_ref_like {
+            return false;
+        }
+
+        match self {
+            Self::Value { type_name, size } => {
+                nullable.unwrap_or(r#type) == *type_name && (parameter.size == 0 || parameter.size == *size)
+            }
+            Self::String => r#type == "System.String",
+            Self::Array { type_name, size } => {
+                r#type.strip_suffix("[]") == Some(*type_name)
+                    && (parameter.element_size == 0 || parameter.element_size == *size)
+            }
+            // Strings and arrays are read from a C string or an array header, not a handle
+            Self::Object => !parameter.is_value_type && r#type != "System.String" && !r#type.ends_with("[]"),
+            Self::Null => !parameter.is_value_type || nullable.is_some(),
+        }
+    }
+}
+impl std::fmt::Display for ArgumentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Value { type_name, size } => write!(f, "{type_name} ({size} bytes)"),
+            Self::String => write!(f, "System.String"),
+            Self::Array { type_name, size } => write!(f, "{type_name}[] ({size} byte elements)"),
+            Self::Object => write!(f, "object handle"),
+            Self::Null => write!(f, "null"),
+        }
+    }
+}
+
+/// A marshalled argument, owning any buffer the pointer refers to until the call returns
+pub struct ManagedValue {
+    kind: ArgumentKind,
+    pointer: *const c_void,
+    _storage: Option<Box<dyn Any>>,
+}
+impl ManagedValue {
+    /// Pass `pointer` as is, the caller keeps what it points to alive
+    pub fn borrowed(kind: ArgumentKind, pointer: *const c_void) -> Self {
+        Self { kind, pointer, _storage: None }
+    }
+
+    /// Pass `pointer` which points into `storage`
+    pub fn owned(kind: ArgumentKind, pointer: *const c_void, storage: Box<dyn Any>) -> Self {
+        Self { kind, pointer, _storage: Some(storage) }
+    }
+
+    /// Passed as `null` for reference types and `Nullable<T>`
+    pub fn null() -> Self {
+        Self::borrowed(ArgumentKind::Null, std::ptr::null())
+    }
+
+    pub fn kind(&self) -> ArgumentKind {
+        self.kind
+    }
+}
+impl Wrapper for ManagedValue {
+    fn as_ptr(&self) -> *const c_void {
+        self.pointer
+    }
+}
+
+/// Header of an array argument, read as a `T[]` by the managed side
+#[repr(C)]
+struct ManagedSlice {
+    data: *const c_void,
+    length: i32,
+}
+
+/// C# arrays are indexed by `int`, longer slices can't be passed
+fn array_length(length: usize) -> Result<i32> {
+    i32::try_from(length).map_err(|_| Error::ArrayTooLong { length })
+}
+
+/// Strings are cut at the first nul since the managed side reads them as C strings
+fn to_cstring(value: impl Into<Vec<u8>>) -> CString {
+    CString::new(value).unwrap_or_else(|err| {
+        let nul = err.nul_position();
+        let mut bytes = err.into_vec();
+        bytes.truncate(nul);
+        CString::new(bytes).unwrap()
+    })
+}
+
 pub trait ManagedParam {
-    fn into_managed_param(self) -> *const c_void;
+    fn into_managed_param(self) -> Result<ManagedValue>;
 }
-impl<A> ManagedParam for &A {
-    fn into_managed_param(self) -> *const c_void {
-        self as *const _ as *const c_void
+/// Passed as a pointer to the value, which must have the same layout as the C# type
+impl<A: ManagedType> ManagedParam for &A {
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        let kind = ArgumentKind::Value { type_name: A::TYPE_NAME, size: A::SIZE };
+        Ok(ManagedValue::borrowed(kind, self as *const _ as *const c_void))
+    }
+}
+impl ManagedParam for &Object {
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        Ok(ManagedValue::borrowed(ArgumentKind::Object, self.as_ptr()))
     }
 }
 impl ManagedParam for Object {
-    fn into_managed_param(self) -> *const c_void {
-        self.as_ptr()
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        // Keep the handle alive until the call returns
+        Ok(ManagedValue::owned(ArgumentKind::Object, self.as_ptr(), Box::new(self)))
+    }
+}
+impl ManagedParam for bool {
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        // Read as a single byte by the managed side
+        let value = Box::new(u8::from(self));
+        let kind = ArgumentKind::Value { type_name: bool::TYPE_NAME, size: bool::SIZE };
+        Ok(ManagedValue::owned(kind, (&raw const *value).cast(), value))
+    }
+}
+impl ManagedParam for &CStr {
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        Ok(ManagedValue::borrowed(ArgumentKind::String, self.as_ptr().cast()))
+    }
+}
+impl ManagedParam for &str {
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        let value = to_cstring(self);
+        Ok(ManagedValue::owned(ArgumentKind::String, value.as_ptr().cast(), Box::new(value)))
+    }
+}
+impl ManagedParam for String {
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        let value = to_cstring(self);
+        Ok(ManagedValue::owned(ArgumentKind::String, value.as_ptr().cast(), Box::new(value)))
+    }
+}
+/// Passed as a `T[]` copied from the slice, `Span<T>` and `ReadOnlySpan<T>` parameters can't be passed to
+impl<T: ManagedType + Copy> ManagedParam for &[T] {
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        let header = Box::new(ManagedSlice {
+            data: self.as_ptr().cast(),
+            length: array_length(self.len())?,
+        });
+        let kind = ArgumentKind::Array { type_name: T::TYPE_NAME, size: T::SIZE };
+        Ok(ManagedValue::owned(kind, (&raw const *header).cast(), header))
+    }
+}
+/// Passed as a `T[]` copied from the vec
+impl<T: ManagedType + Copy + 'static> ManagedParam for Vec<T> {
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        let header = ManagedSlice {
+            data: self.as_ptr().cast(),
+            length: array_length(self.len())?,
+        };
+        // Moving the vec into the box doesn't move its buffer
+        let storage = Box::new((self, header));
+        let kind = ArgumentKind::Array { type_name: T::TYPE_NAME, size: T::SIZE };
+        Ok(ManagedValue::owned(kind, (&raw const storage.1).cast(), storage))
+    }
+}
+/// `None` is passed as `null`, for value types the parameter should be `Nullable<T>`
+impl<T: ManagedParam> ManagedParam for Option<T> {
+    fn into_managed_param(self) -> Result<ManagedValue> {
+        match self {
+            Some(value) => value.into_managed_param(),
+            None => Ok(ManagedValue::null()),
+        }
+    }
+}
+
+/// A rust type with a known C# counterpart
+///
+/// Values are passed to the managed side by pointer so structs must be `#[repr(C)]` with the
+/// same layout as the C# struct, which should use `[StructLayout(LayoutKind.Sequential)]`.
+///
+/// ```ignore
+/// #[repr(C)]
+/// struct Vector3 { x: f32, y: f32, z: f32 }
+/// impl ManagedType for Vector3 {
+///     const TYPE_NAME: &'static str = "Engine.Vector3";
+/// }
+/// ```
+pub trait ManagedType: Sized {
+    /// Full name of the C# type, e.g. `System.Single`
+    const TYPE_NAME: &'static str;
+    /// Size of the value, compared with the marshalled size of C# structs
+    const SIZE: usize = std::mem::size_of::<Self>();
+}
+
+macro_rules! managed_type {
+    ($($type: ty => $name: literal),* $(,)?) => {
+        $(impl ManagedType for $type {
+            const TYPE_NAME: &'static str = $name;
+        })*
+    };
+}
+
+managed_type!(
+    i8 => "System.SByte",
+    i16 => "System.Int16",
+    i32 => "System.Int32",
+    i64 => "System.Int64",
+    isize => "System.IntPtr",
+    u8 => "System.Byte",
+    u16 => "System.UInt16",
+    u32 => "System.UInt32",
+    u64 => "System.UInt64",
+    usize => "System.UIntPtr",
+    f32 => "System.Single",
+    f64 => "System.Double",
+    bool => "System.Boolean",
+);
+
+/// A `#[repr(C)]` struct shared with C#, see [`derive@crate::ManagedStruct`]
+pub trait ManagedStruct: ManagedType {
+    /// Source of the matching C# struct
+    fn csharp_source() -> String;
+}
+
+/// Parameter types of a method, used to select an overload from rust argument types
+///
+/// ```ignore
+/// // Selects `Damage(float)` over `Damage(int)`
+/// runtime.get_method_typed::<f32>(&script, "Damage")?;
+/// ```
+pub trait ManagedSignature {
+    fn type_names() -> Vec<String>;
+}
+impl ManagedSignature for () {
+    fn type_names() -> Vec<String> {
+        Vec::new()
+    }
+}
+impl<A: ManagedType> ManagedSignature for A {
+    fn type_names() -> Vec<String> {
+        Vec::from([A::TYPE_NAME.to_string()])
+    }
+}
+
+macro_rules! managed_signature {
+    ($($name: ident),+) => {
+        impl<$($name: ManagedType),+> ManagedSignature for ($($name,)+) {
+            fn type_names() -> Vec<String> {
+                Vec::from([$($name::TYPE_NAME.to_string()),+])
+            }
+        }
+    };
+}
+
+managed_signature!(A);
+managed_signature!(A, B);
+managed_signature!(A, B, C);
+managed_signature!(A, B, C, D);
+managed_signature!(A, B, C, D, E);
+managed_signature!(A, B, C, D, E, F);
+managed_signature!(A, B, C, D, E, F, G);
+managed_signature!(A, B, C, D, E, F, G, H);
+managed_signature!(A, B, C, D, E, F, G, H, I);
+managed_signature!(A, B, C, D, E, F, G, H, I, J);
+managed_signature!(A, B, C, D, E, F, G, H, I, J, K);
+managed_signature!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Marshalled arguments of a call
+///
+/// Can be built at runtime when the number of arguments isn't known ahead of time, e.g. in editor tooling.
+/// Referenced values must outlive the call.
+///
+/// ```ignore
+/// let mut args = ManagedArgs::new();
+/// args.push(&damage)?.push("fire")?;
+/// method.invoke(args)?;
+/// ```
+#[derive(Default)]
+pub struct ManagedArgs {
+    values: Vec<ManagedValue>,
+    pointers: Vec<*const c_void>,
+}
+impl ManagedArgs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, value: impl ManagedParam) -> Result<&mut Self> {
+        let value = value.into_managed_param()?;
+        self.pointers.push(value.as_ptr());
+        self.values.push(value);
+        Ok(self)
+    }
+
+    pub fn as_slice(&self) -> &[*const c_void] {
+        &self.pointers
+    }
+
+    pub fn kinds(&self) -> impl Iterator<Item = ArgumentKind> + '_ {
+        self.values.iter().map(ManagedValue::kind)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pointers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pointers.is_empty()
+    }
+}
+impl FromIterator<ManagedValue> for ManagedArgs {
+    fn from_iter<I: IntoIterator<Item = ManagedValue>>(iter: I) -> Self {
+        let values = iter.into_iter().collect::<Vec<_>>();
+        let pointers = values.iter().map(ManagedValue::as_ptr).collect();
+        Self { values, pointers }
     }
 }
 
 pub trait ManagedParams {
-    fn into_managed_params(self) -> Vec<*const c_void>;
+    fn into_managed_params(self) -> Result<ManagedArgs>;
 }
 impl ManagedParams for () {
-    fn into_managed_params(self) -> Vec<*const c_void> {
-        Vec::new()
+    fn into_managed_params(self) -> Result<ManagedArgs> {
+        Ok(ManagedArgs::default())
     }
 }
 impl<A: ManagedParam> ManagedParams for A {
-    fn into_managed_params(self) -> Vec<*const c_void> {
-        Vec::from([self.into_managed_param()])
+    fn into_managed_params(self) -> Result<ManagedArgs> {
+        Ok(ManagedArgs::from_iter([self.into_managed_param()?]))
     }
 }
-impl<A: ManagedParam, B: ManagedParam> ManagedParams for (A, B) {
-    fn into_managed_params(self) -> Vec<*const c_void> {
-        Vec::from([self.0.into_managed_param(), self.1.into_managed_param()])
+impl ManagedParams for ManagedArgs {
+    fn into_managed_params(self) -> Result<ManagedArgs> {
+        Ok(self)
     }
 }
 
+macro_rules! managed_params {
+    ($($name: ident),+) => {
+        impl<$($name: ManagedParam),+> ManagedParams for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_managed_params(self) -> Result<ManagedArgs> {
+                let ($($name,)+) = self;
+                Ok(ManagedArgs::from_iter([$($name.into_managed_param()?),+]))
+            }
+        }
+    };
+}
+
+managed_params!(A, B);
+managed_params!(A, B, C);
+managed_params!(A, B, C, D);
+managed_params!(A, B, C, D, E);
+managed_params!(A, B, C, D, E, F);
+managed_params!(A, B, C, D, E, F, G);
+managed_params!(A, B, C, D, E, F, G, H);
+managed_params!(A, B, C, D, E, F, G, H, I);
+managed_params!(A, B, C, D, E, F, G, H, I, J);
+managed_params!(A, B, C, D, E, F, G, H, I, J, K);
+managed_params!(A, B, C, D, E, F, G, H, I, J, K, L);
+
 #[derive(bevy::prelude::Component)]
 pub struct Script {
     pub(crate) index: usize,
@@ -135,6 +524,7 @@ impl AsRef<Object> for Script {
 #[allow(dead_code)]
 #[derive(Resource)]
 pub struct Runtime {
+    pub config: Config,
     pub paths: Paths,
     pub versions: Versions,
 
@@ -143,9 +533,13 @@ pub struct Runtime {
 
     pub scope: Option<Scope>,
     pub assemblies: HashMap<AssemblyType, Assembly>,
+    /// Third party assemblies, e.g. NuGet packages, loaded alongside the managed assemblies
+    pub dependencies: Vec<Assembly>,
 
     pub fullname_to_script: HashMap<Cow<'static, str>, usize>,
     pub scripts: Vec<Rc<Type>>,
+    /// Classes resolved by name for static access that aren't registered scripts
+    pub(crate) classes: RefCell<HashMap<String, Rc<Type>>>,
 }
 
 // Bevy garuntees that one system at a time is using the resource.
@@ -160,43 +554,57 @@ impl Drop for Runtime {
 }
 
 impl Runtime {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Result<Self> {
-        let exe_parent = std::env::current_exe().unwrap();
-        let exe_dir = exe_parent.parent().unwrap();
-
-        let dotnet = dotnet::get_path().ok_or(Error::PathNotFound)?;
-        let hostfxr_path = dotnet.join("host").join("fxr");
+    /// Host the runtime with the managed project layout described by `config`
+    pub fn new(config: Config) -> Result<Self> {
+        let exe_parent = std::env::current_exe()?;
+        let exe_dir = exe_parent.parent().ok_or(Error::PathNotFound)?;
+
+        let requested = discovery::requested(&config.version).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid framework version in the managed config")
+        })?;
+        // Distributed games only use the runtime shipped next to the executable
+        #[cfg(feature = "distribute")]
+        let installs = discovery::Install::new(exe_dir.join(discovery::APP_LOCAL))
+            .into_iter()
+            .collect::<Vec<_>>();
+        #[cfg(not(feature = "distribute"))]
+        let installs = discovery::installs();
+        if installs.is_empty() {
+            return Err(Error::DotnetNotFound);
+        }
+        let (install, framework) = installs
+            .into_iter()
+            .find_map(|install| {
+                let framework = install.resolve_runtime(&requested, config.roll_forward)?.clone();
+                Some((install, framework))
+            })
+            .ok_or_else(|| Error::FrameworkNotFound {
+                requested: requested.to_string(),
+                roll_forward: config.roll_forward,
+            })?;
 
         let versions = Versions {
-            framework: FRAMEWORK.to_string(),
+            framework: framework.to_string(),
             net: NET.to_string(),
         };
 
         log::debug!("Versions:");
         log::debug!("    net: {}", versions.net);
+        log::debug!("    built with: {FRAMEWORK}");
+        log::debug!("    requested: {requested} ({:?})", config.roll_forward);
         log::debug!("    framework: {}", versions.framework);
 
         let paths = Paths {
             exe: exe_dir.to_path_buf(),
-            dotnet,
+            hostfxr: install
+                .hostfxr_path()
+                .ok_or_else(|| Error::HostfxrNotFound(install.root.join("host").join("fxr")))?,
+            dotnet: install.root,
             config: exe_dir.join("Runtime.runtimeconfig.json"),
             dll: exe_dir.join("Runtime.dll"),
-            hostfxr: hostfxr_path.join(FRAMEWORK).join({
-                #[cfg(target_os = "windows")]
-                {
-                    "hostfxr.dll"
-                }
-                #[cfg(target_os = "linux")]
-                {
-                    "hostfxr.so"
-                }
-                #[cfg(target_os = "linux")]
-                {
-                    "hostfxr.dylib"
-                }
-            }),
-            managed: PathBuf::from("assets"),
+            engine: config.engine.root().to_path_buf(),
+            scripts: config.scripts.root().to_path_buf(),
+            output: exe_dir.join(&config.output),
         };
 
         log::debug!("Paths:");
@@ -204,21 +612,43 @@ impl Runtime {
         log::debug!("    hostfxr: {}", paths.hostfxr.display());
         log::debug!("    config: {}", paths.config.display());
         log::debug!("    dll: {}", paths.dll.display());
-        log::debug!("    managed: {}", paths.managed.display());
+        log::debug!("    engine: {}", paths.engine.display());
+        log::debug!("    scripts: {}", paths.scripts.display());
+        log::debug!("    output: {}", paths.output.display());
+
+        // hostfxr resolves the framework itself so the runtime config has to describe the same policy
+        let runtime_config = format_runtime_config(&versions.net, &requested, config.roll_forward);
+        if std::fs::read_to_string(&paths.config).ok().as_deref() != Some(runtime_config.as_str()) {
+            log::debug!("[write] {}", paths.config.display());
+            if let Err(err) = std::fs::write(&paths.config, &runtime_config) {
+                log::warn!("failed to update {}: {err}", paths.config.display());
+            }
+        }
 
-        let host = Hostfxr::new(&paths);
+        let host = Hostfxr::new(&paths)?;
 
         log::debug!("[bind] Runtime.dll methods");
+        let library = match RuntimeLibrary::new(&host) {
+            Ok(library) => library,
+            Err(err) => {
+                unsafe { host.lib.hostfxr_close(host.ctx) };
+                return Err(err);
+            }
+        };
+
         Ok(Self {
-            library: RuntimeLibrary::new(&host),
+            library,
             host,
+            config,
             paths,
             versions,
             scope: None,
 
             fullname_to_script: Default::default(),
             assemblies: Default::default(),
+            dependencies: Default::default(),
             scripts: Default::default(),
+            classes: Default::default(),
         })
     }
 
@@ -233,6 +663,49 @@ impl Runtime {
         }
     }
 
+    /// Create a new instance of a script with the first constructor taking as many parameters as `args`
+    ///
+    /// Non-public constructors are included so scripts can require initialization arguments.
+    /// The arguments are checked against the constructor's parameters like [`Method::check`].
+    pub fn create_with(&self, name: impl AsRef<str>, args: impl ManagedParams) -> Result<Script> {
+        self.create_with_constructor(name, None, args)
+    }
+
+    /// Create a new instance of a script with the constructor taking exactly these parameter types
+    pub fn create_with_signature(
+        &self,
+        name: impl AsRef<str>,
+        parameters: &[&str],
+        args: impl ManagedParams,
+    ) -> Result<Script> {
+        let parameters = parameters.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        self.create_with_constructor(name, Some(parameters.as_slice()), args)
+    }
+
+    fn create_with_constructor(
+        &self,
+        name: impl AsRef<str>,
+        parameters: Option<&[String]>,
+        args: impl ManagedParams,
+    ) -> Result<Script> {
+        let index = self
+            .fullname_to_script
+            .get(name.as_ref())
+            .copied()
+            .ok_or(Error::ClassNotRegistered)?;
+        let script = &self.scripts[index];
+        let args = args.into_managed_params()?;
+
+        let constructor = self.library.get_constructor_info(&script.class, parameters, args.len())?;
+        check_arguments(&constructor, &args)?;
+
+        let instance = self
+            .library
+            .new_object_with(&script.class, parameters, args.as_slice())?
+            .ok_or(Error::UnknownManaged)?;
+        Ok(Script { index, instance })
+    }
+
     pub fn register(&mut self, name: impl AsRef<str>) -> Result<()> {
         let scripts_asm = self.assemblies.get(&AssemblyType::Scripts).ok_or(Error::AssemblyNotLoaded)?;
 
@@ -250,25 +723,159 @@ impl Runtime {
             name,
             class,
             methods: Default::default(),
+            fields: Default::default(),
+            properties: Default::default(),
             metadata,
         }));
 
         Ok(())
     }
 
+    /// Register every class in the `Scripts` assembly deriving from [`BEHAVIOUR`] or marked with [`SCRIPT_ATTRIBUTE`]
+    ///
+    /// Classes are registered by their full name, e.g. `Game.Player`, which is also returned.
+    /// Classes that are already registered are skipped.
+    pub fn register_all(&mut self) -> Result<Vec<String>> {
+        let engine_asm = self.assemblies.get(&AssemblyType::Engine).ok_or(Error::AssemblyNotLoaded)?;
+        let scripts_asm = self.assemblies.get(&AssemblyType::Scripts).ok_or(Error::AssemblyNotLoaded)?;
+
+        // The engine api is user editable so either marker may be missing
+        let find = |name: &str| match self.library.get_class(engine_asm, name) {
+            Err(Error::ClassNotFound) => Ok(None),
+            other => other,
+        };
+        let behaviour = find(BEHAVIOUR)?;
+        let attribute = find(SCRIPT_ATTRIBUTE)?;
+
+        let mut names = Vec::new();
+        for name in self.library.get_types(scripts_asm)? {
+            if self.fullname_to_script.contains_key(name.as_str()) {
+                continue;
+            }
+
+            let Some(class) = self.library.get_class(scripts_asm, &name)? else { continue };
+            let is_script = match &behaviour {
+                Some(behaviour) => self.library.is_assignable_from(behaviour, &class)?,
+                None => false,
+            } || match &attribute {
+                Some(attribute) => self.library.has_attribute(&class, attribute)?,
+                None => false,
+            };
+
+            if is_script {
+                names.push(name);
+            }
+        }
+
+        for name in &names {
+            log::debug!("[register] {name}");
+            self.register(name)?;
+        }
+        Ok(names)
+    }
+
     pub fn load(&mut self, assembly: AssemblyType) -> Result<()> {
         // TODO: Make the load more dynamic to include more assemblies
         if let Some(scope) = self.scope.as_ref() {
-            let asm = self.library.load_from_path(scope, assembly.path(&self.paths.exe))?.ok_or(Error::PathNotFound)?;
+            let asm = self.library.load_from_path(scope, assembly.path(&self.paths.output))?.ok_or(Error::PathNotFound)?;
+            self.assemblies.insert(assembly, asm);
+        }
+        Ok(())
+    }
+
+    /// Load every dll in the output directory other than `Engine.dll` and `Scripts.dll` into the current scope
+    ///
+    /// These are the dependencies, like NuGet packages, copied next to the managed assemblies
+    /// when they are built.
+    pub fn load_dependencies(&mut self) -> Result<()> {
+        let dependencies = self.dependency_paths()?;
+        if let Some(scope) = self.scope.as_ref() {
+            for path in dependencies {
+                log::debug!("[load] {}", path.display());
+                let asm = self.library.load_from_path(scope, &path)?.ok_or(Error::PathNotFound)?;
+                self.dependencies.push(asm);
+            }
+        }
+        Ok(())
+    }
+
+    fn dependency_paths(&self) -> Result<Vec<PathBuf>> {
+        if !self.paths.output.exists() {
+            return Ok(Vec::new());
+        }
+
+        let managed = [AssemblyType::Engine.to_string(), AssemblyType::Scripts.to_string()];
+        Ok(self
+            .paths
+            .output
+            .read_dir()?
+            .filter_map(std::result::Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().is_some_and(|ext| ext == "dll")
+                    && !path
+                        .file_stem()
+                        .is_some_and(|stem| managed.iter().any(|name| stem == name.as_str()))
+            })
+            .collect())
+    }
+
+    /// Load an in-memory assembly image, e.g. produced by [`Runtime::compile`], into the current scope
+    pub fn load_bytes(&mut self, assembly: AssemblyType, bytes: &[u8]) -> Result<()> {
+        if let Some(scope) = self.scope.as_ref() {
+            let asm = self.library.load_from_bytes(scope, bytes)?.ok_or(Error::AssemblyNotLoaded)?;
             self.assemblies.insert(assembly, asm);
         }
         Ok(())
     }
 
+    /// Compile C# sources with Roslyn inside the hosted runtime and load the result into the current scope
+    ///
+    /// This does not need MSBuild or a project file. Sources are compiled against the loaded
+    /// dependencies and `Scripts` also against `Engine.dll` when it exists. The image is written
+    /// to the output directory so later compilations can reference it. The nullable context,
+    /// language version and defines of the matching [`Project`] are applied like in its generated project.
+    pub fn compile(
+        &mut self,
+        assembly: AssemblyType,
+        sources: impl IntoIterator<Item = impl AsRef<Path>>,
+    ) -> Result<()> {
+        let sources = sources
+            .into_iter()
+            .map(|v| v.as_ref().to_path_buf())
+            .collect::<Vec<_>>();
+
+        let mut references = self.dependency_paths()?;
+        if assembly == AssemblyType::Scripts {
+            let engine = AssemblyType::Engine.path(&self.paths.output);
+            if engine.exists() {
+                references.push(engine);
+            }
+        }
+
+        let project = match assembly {
+            AssemblyType::Engine => &self.config.engine,
+            AssemblyType::Scripts => &self.config.scripts,
+        };
+
+        let now = std::time::Instant::now();
+        let compilation = self.library.compile(assembly, &sources, &references, project, cfg!(debug_assertions))?;
+        log::debug!("[compile] {assembly} {:.3} s", now.elapsed().as_secs_f64());
+
+        compilation.diagnostics.iter().for_each(Diagnostic::log);
+        let image = compilation.assembly.ok_or(Error::CompilationFailed)?;
+
+        std::fs::create_dir_all(&self.paths.output)?;
+        std::fs::write(assembly.path(&self.paths.output), &image)?;
+        self.load_bytes(assembly, &image)
+    }
+
     pub fn clear(&mut self) -> Result<()> {
         self.scripts.truncate(0);
         self.fullname_to_script = HashMap::new();
+        self.classes.borrow_mut().clear();
         self.assemblies.clear();
+        self.dependencies.clear();
 
         if let Some(scope) = self.scope.replace(self.library.create_scope()) {
             let mut err: i32 = -1;
@@ -279,38 +886,309 @@ impl Runtime {
         Ok(())
     }
 
+    /// Resolve the first method `name` with `args` parameters
+    ///
+    /// Use [`Runtime::get_method_with_signature`] when the method is overloaded
     pub fn get_method<'s>(
         &self,
         handle: &'s Script,
         name: impl std::fmt::Display,
         args: i32,
     ) -> Result<Option<Invokable<'s>>> {
-        if let Some(script) = self.scripts.get(handle.index) {
-            return match script.methods.borrow_mut().entry((name.to_string(), args)) {
-                Entry::Occupied(entry) => Ok(Some(Invokable {
-                    instance: &handle.instance,
-                    method: entry.get().clone(),
-                    invoke: self.library.runtime_invoke,
-                })),
+        match self.scripts.get(handle.index) {
+            Some(script) => self.resolve_method(script, Some(&handle.instance), name.to_string(), Signature::Arity(args)),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolve the method `name` with exactly these parameter types
+    ///
+    /// Types are named by their full name, e.g. `System.Single`, or their short name, e.g. `Single`.
+    pub fn get_method_with_signature<'s>(
+        &self,
+        handle: &'s Script,
+        name: impl std::fmt::Display,
+        parameters: &[&str],
+    ) -> Result<Option<Invokable<'s>>> {
+        let signature = Signature::Parameters(parameters.iter().map(|v| v.to_string()).collect());
+        match self.scripts.get(handle.index) {
+            Some(script) => self.resolve_method(script, Some(&handle.instance), name.to_string(), signature),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolve the method `name` with parameter types matching the rust types in `S`
+    pub fn get_method_typed<'s, S: ManagedSignature>(
+        &self,
+        handle: &'s Script,
+        name: impl std::fmt::Display,
+    ) -> Result<Option<Invokable<'s>>> {
+        let signature = Signature::Parameters(S::type_names());
+        match self.scripts.get(handle.index) {
+            Some(script) => self.resolve_method(script, Some(&handle.instance), name.to_string(), signature),
+            None => Ok(None),
+        }
+    }
+
+    /// Resolve the static method `name` with exactly these parameter types on the class `class_name`
+    ///
+    /// The class can be any class in the `Scripts` or `Engine` assembly, it doesn't have to be registered.
+    pub fn get_static_method(
+        &self,
+        class_name: impl AsRef<str>,
+        name: impl std::fmt::Display,
+        parameters: &[&str],
+    ) -> Result<Option<Invokable<'static>>> {
+        let class = self.get_class(class_name)?;
+        let signature = Signature::Parameters(parameters.iter().map(|v| v.to_string()).collect());
+        self.resolve_method(&class, None, name.to_string(), signature)
+    }
+
+    fn resolve_method<'s>(
+        &self,
+        r#type: &Type,
+        instance: Option<&'s Object>,
+        name: String,
+        signature: Signature,
+    ) -> Result<Option<Invokable<'s>>> {
+        Ok(self.cached_method(r#type, name, signature)?.map(|method| Invokable {
+            instance,
+            method,
+            invoke: self.library.runtime_invoke,
+            reflection: self.library.runtime_invoke_reflection,
+        }))
+    }
+
+    fn cached_method(&self, r#type: &Type, name: String, signature: Signature) -> Result<Option<Rc<Method>>> {
+        match r#type.methods.borrow_mut().entry((name, signature)) {
+            Entry::Occupied(entry) => Ok(Some(entry.get().clone())),
+            Entry::Vacant(entry) => {
+                let (name, signature) = entry.key();
+                let method = match signature {
+                    Signature::Arity(args) => self.library.get_method(&r#type.class, name, *args)?,
+                    Signature::Parameters(parameters) => {
+                        self.library.get_method_with_signature(&r#type.class, name, parameters)?
+                    }
+                };
+                let Some(method) = method else { return Ok(None) };
+                Ok(Some(entry.insert(Rc::new(method)).clone()))
+            }
+        }
+    }
+
+    /// Invoke the method `name` on every script with one call into the runtime, e.g. `Update` each frame
+    ///
+    /// The method is resolved for the class of each script like [`Runtime::get_method`], with as many
+    /// parameters as there are `args`, and scripts whose class has no such method are skipped. Every call
+    /// receives the same `args`.
+    ///
+    /// Returns the scripts that failed, by their position in `scripts`, e.g. with [`Error::InvocationFailed`]
+    /// when the method threw or [`Error::ArgumentTypeMismatch`] when their class's method doesn't take
+    /// `args`. The other scripts are still invoked.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_cs_managed::{Runtime, Script};
+    /// fn update(query: Query<&Script>, runtime: Res<Runtime>, time: Res<Time>) {
+    ///     for (position, error) in runtime.invoke_all(&query, "Update", &time.delta_secs()).unwrap() {
+    ///         eprintln!("script {position} failed to update: {error}");
+    ///     }
+    /// }
+    /// ```
+    pub fn invoke_all<'s>(
+        &self,
+        scripts: impl IntoIterator<Item = &'s Script>,
+        name: impl std::fmt::Display,
+        args: impl ManagedParams,
+    ) -> Result<Vec<(usize, Error)>> {
+        let name = name.to_string();
+        let params = args.into_managed_params()?;
+        let signature = Signature::Arity(params.len() as i32);
+
+        // Resolved once per class, the handles stay alive until the batch returns
+        let mut resolved: HashMap<usize, Option<Rc<Method>>> = HashMap::new();
+        let mut failed = Vec::new();
+        let mut positions = Vec::new();
+        let mut methods = Vec::new();
+        let mut instances = Vec::new();
+        for (position, script) in scripts.into_iter().enumerate() {
+            let method = match resolved.entry(script.index) {
+                Entry::Occupied(entry) => entry.into_mut(),
                 Entry::Vacant(entry) => {
-                    let method = Rc::new(match self.library.get_method(
-                        &script.class,
-                        &entry.key().0,
-                        args,
-                    )? {
-                        Some(m) => m,
-                        None => return Ok(None)
-                    });
-                    entry.insert(method.clone());
-                    Ok(Some(Invokable {
-                        instance: &handle.instance,
-                        method,
-                        invoke: self.library.runtime_invoke,
-                    }))
+                    let r#type = self.scripts.get(script.index).ok_or(Error::ClassNotRegistered)?;
+                    entry.insert(self.cached_method(r#type, name.clone(), signature.clone())?)
                 }
             };
+            let Some(method) = method else { continue };
+            // Only the scripts of a class whose method doesn't take `args` fail
+            if let Err(error) = method.check(&params) {
+                failed.push((position, error));
+                continue;
+            }
+
+            positions.push(position);
+            methods.push(method.as_ptr());
+            instances.push(script.instance.as_ptr());
+        }
+        if methods.is_empty() {
+            return Ok(failed);
+        }
+
+        let mut errors = vec![0; methods.len()];
+        let mut err: i32 = -1;
+        unsafe {
+            (self.library.runtime_invoke_batch)(
+                methods.len() as i32,
+                methods.as_ptr(),
+                instances.as_ptr(),
+                params.as_slice().as_ptr(),
+                errors.as_mut_ptr(),
+                &raw mut err,
+            )
+        };
+        if err > 0 { return Err(Error::from(err)); }
+
+        failed.extend(
+            positions
+                .into_iter()
+                .zip(errors)
+                .filter(|(_, err)| *err > 0)
+                .map(|(position, err)| (position, Error::from(err))),
+        );
+        failed.sort_by_key(|(position, _)| *position);
+        Ok(failed)
+    }
+
+    /// Resolve a class by its full name, registered scripts first and then the `Scripts` and `Engine` assemblies
+    ///
+    /// Classes that aren't scripts are cached separately so they aren't returned by [`Runtime::create`].
+    pub fn get_class(&self, name: impl AsRef<str>) -> Result<Rc<Type>> {
+        let name = name.as_ref();
+        if let Some(index) = self.fullname_to_script.get(name).copied() {
+            return Ok(self.scripts[index].clone());
+        }
+        if let Some(class) = self.classes.borrow().get(name) {
+            return Ok(class.clone());
+        }
+
+        for assembly in [AssemblyType::Scripts, AssemblyType::Engine] {
+            let Some(asm) = self.assemblies.get(&assembly) else { continue };
+            let class = match self.library.get_class(asm, name) {
+                Ok(Some(class)) => class,
+                Ok(None) | Err(Error::ClassNotFound) => continue,
+                Err(err) => return Err(err),
+            };
+
+            let r#type = Rc::new(Type {
+                name: name.to_string().into(),
+                metadata: self.library.get_meta_data(&class)?,
+                class,
+                methods: Default::default(),
+                fields: Default::default(),
+                properties: Default::default(),
+            });
+            self.classes.borrow_mut().insert(name.to_string(), r#type.clone());
+            return Ok(r#type);
         }
-        Ok(None)
+
+        Err(Error::ClassNotFound)
+    }
+
+    pub fn get_static_field_value<A: DeserializeOwned>(
+        &self,
+        class_name: impl AsRef<str>,
+        name: impl AsRef<str>,
+    ) -> Result<Option<A>> {
+        let class = self.get_class(class_name)?;
+        self.library.get_static_value(self.library.get_static_field_value, &class.class, name)
+    }
+
+    pub fn set_static_field_value(
+        &self,
+        class_name: impl AsRef<str>,
+        name: impl AsRef<str>,
+        value: impl ManagedParam,
+    ) -> Result<()> {
+        let class = self.get_class(class_name)?;
+        let library = &self.library;
+        library.set_static_value(library.get_static_field_info, library.set_static_field_value, &class.class, name, value)
+    }
+
+    pub fn get_static_property_value<A: DeserializeOwned>(
+        &self,
+        class_name: impl AsRef<str>,
+        name: impl AsRef<str>,
+    ) -> Result<Option<A>> {
+        let class = self.get_class(class_name)?;
+        self.library.get_static_value(self.library.get_static_property_value, &class.class, name)
+    }
+
+    pub fn set_static_property_value(
+        &self,
+        class_name: impl AsRef<str>,
+        name: impl AsRef<str>,
+        value: impl ManagedParam,
+    ) -> Result<()> {
+        let class = self.get_class(class_name)?;
+        let library = &self.library;
+        library.set_static_value(library.get_static_property_info, library.set_static_property_value, &class.class, name, value)
+    }
+
+    /// Resolve the instance field `name` of the class `class_name`, cached on the class like methods
+    ///
+    /// Reading and writing through the accessor skips the name lookup and reflection done by
+    /// [`Object::get_field_value`] and [`Object::set_field_value`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_cs_managed::{Runtime, Script};
+    /// fn read(query: Query<&Script>, runtime: Res<Runtime>) {
+    ///     let Ok(health) = runtime.field_accessor("Player", "Health") else { return };
+    ///     for script in &query {
+    ///         let mut value = 0.0f32;
+    ///         health.get_into(script, &mut value).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn field_accessor(&self, class_name: impl AsRef<str>, name: impl AsRef<str>) -> Result<FieldAccessor> {
+        let class = self.get_class(class_name)?;
+        let name = name.as_ref();
+        if let Some(accessor) = class.fields.borrow().get(name) {
+            return Ok(FieldAccessor(accessor.clone()));
+        }
+
+        let accessor = Rc::new(self.library.get_accessor(self.library.get_field_accessor, &class.class, name)?);
+        class.fields.borrow_mut().insert(name.to_string(), accessor.clone());
+        Ok(FieldAccessor(accessor))
+    }
+
+    /// Resolve the instance property `name` of the class `class_name`, cached on the class like methods
+    ///
+    /// See [`Runtime::field_accessor`]
+    pub fn property_accessor(&self, class_name: impl AsRef<str>, name: impl AsRef<str>) -> Result<PropertyAccessor> {
+        let class = self.get_class(class_name)?;
+        let name = name.as_ref();
+        if let Some(accessor) = class.properties.borrow().get(name) {
+            return Ok(PropertyAccessor(accessor.clone()));
+        }
+
+        let accessor = Rc::new(self.library.get_accessor(self.library.get_property_accessor, &class.class, name)?);
+        class.properties.borrow_mut().insert(name.to_string(), accessor.clone());
+        Ok(PropertyAccessor(accessor))
+    }
+
+    /// Describe the class of a script, its fields, properties, methods and attributes
+    pub fn get_type_info(&self, handle: &Script) -> Result<TypeInfo> {
+        let script = self.scripts.get(handle.index).ok_or(Error::ClassNotRegistered)?;
+        self.library.get_type_info(&script.class)
+    }
+
+    /// Describe every type in a loaded assembly
+    pub fn describe(&self, assembly: AssemblyType) -> Result<Vec<TypeInfo>> {
+        let asm = self.assemblies.get(&assembly).ok_or(Error::AssemblyNotLoaded)?;
+        self.library.describe_assembly(asm)
     }
 
     pub fn get_meta_data(&mut self, handle: &Script) -> &MetaData {
@@ -334,8 +1212,16 @@ impl Runtime {
         &self.paths.hostfxr
     }
 
-    pub fn get_managed_path(&self) -> &Path {
-        &self.paths.managed
+    pub fn get_engine_path(&self) -> &Path {
+        &self.paths.engine
+    }
+
+    pub fn get_scripts_path(&self) -> &Path {
+        &self.paths.scripts
+    }
+
+    pub fn get_output_path(&self) -> &Path {
+        &self.paths.output
     }
 
     pub fn get_framework_version(&self) -> &str {
@@ -347,12 +1233,31 @@ impl Runtime {
     }
 }
 
+/// Contents of `Runtime.runtimeconfig.json` requesting at least `requested` with the given roll forward policy
+fn format_runtime_config(net: &str, requested: &SemVer, roll_forward: RollForward) -> String {
+    serde_json::to_string_pretty(&serde_json::json!({
+        "runtimeOptions": {
+            "tfm": net,
+            "rollForward": roll_forward.as_runtime_config(),
+            "framework": {
+                "name": "Microsoft.NETCore.App",
+                "version": requested.to_string(),
+            }
+        }
+    }))
+    .unwrap()
+}
+
 pub type Destroy = unsafe extern "system" fn(*const c_void) -> i32;
 pub type Unload = unsafe extern "system" fn(*const c_void, *mut i32) -> i32;
 pub type SetFieldValue =
     unsafe extern "system" fn(*const c_void, *const c_void, *const c_void, *mut i32) -> i32;
 pub type GetFieldValue =
     unsafe extern "system" fn(*const c_void, *const c_void, *mut *const c_void, *mut i32) -> i32;
+pub type GetValueInto =
+    unsafe extern "system" fn(*const c_void, *const c_void, *mut c_void, i32, *mut i32) -> i32;
+pub type GetAccessor =
+    unsafe extern "system" fn(*const c_void, *const c_void, *mut *const c_void, *mut i32) -> i32;
 pub type Invoke =
     unsafe extern "system" fn(*const c_void, *const c_void, *const *const c_void, *mut i32) -> i32;
 
@@ -364,115 +1269,316 @@ pub struct RuntimeLibrary {
     pub(crate) create_scope: unsafe extern "system" fn(*const c_void, *mut *const c_void) -> i32,
     pub(crate) load_from_path:
         unsafe extern "system" fn(*const c_void, *const c_void, *mut *const c_void, *mut i32) -> i32,
+    pub(crate) load_from_bytes:
+        unsafe extern "system" fn(*const c_void, *const u8, i32, *mut *const c_void, *mut i32) -> i32,
     pub(crate) unload_scope: Unload,
 
+    pub(crate) compile: unsafe extern "system" fn(
+        *const c_void,
+        *mut *const c_void,
+        *mut i32,
+        *mut *const c_void,
+        *mut i32,
+    ) -> i32,
+
     pub(crate) get_class:
         unsafe extern "system" fn(*const c_void, *const c_void, *mut *const c_void, *mut i32) -> i32,
 
     pub(crate) new: unsafe extern "system" fn(*const c_void, *mut *const c_void, *mut i32) -> i32,
+    pub(crate) new_with_args: unsafe extern "system" fn(
+        *const c_void,
+        *const c_void,
+        i32,
+        *const *const c_void,
+        *mut *const c_void,
+        *mut i32,
+    ) -> i32,
+    pub(crate) get_constructor_info:
+        unsafe extern "system" fn(*const c_void, *const c_void, i32, *mut *const c_void, *mut i32) -> i32,
     pub(crate) is_assignable_from:
         unsafe extern "system" fn(*const c_void, *const c_void, *mut i32, *mut i32) -> i32,
+    pub(crate) has_attribute:
+        unsafe extern "system" fn(*const c_void, *const c_void, *mut i32, *mut i32) -> i32,
+    pub(crate) get_types: unsafe extern "system" fn(*const c_void, *mut *const c_void, *mut i32) -> i32,
+    pub(crate) get_type_info: unsafe extern "system" fn(*const c_void, *mut *const c_void, *mut i32) -> i32,
+    pub(crate) describe_assembly: unsafe extern "system" fn(*const c_void, *mut *const c_void, *mut i32) -> i32,
     pub(crate) get_method:
         unsafe extern "system" fn(*const c_void, *const c_void, i32, *mut *const c_void, *mut i32) -> i32,
+    pub(crate) get_method_with_signature: unsafe extern "system" fn(
+        *const c_void,
+        *const c_void,
+        *const c_void,
+        *mut *const c_void,
+        *mut i32,
+    ) -> i32,
+    pub(crate) get_method_info: unsafe extern "system" fn(*const c_void, *mut *const c_void, *mut i32) -> i32,
     pub(crate) get_meta_data: unsafe extern "system" fn(*const c_void, *mut *const c_void, *mut i32) -> i32,
     pub(crate) set_field_value: SetFieldValue,
     pub(crate) get_field_value: GetFieldValue,
     pub(crate) set_property_value: SetFieldValue,
     pub(crate) get_property_value: GetFieldValue,
+    pub(crate) get_field_value_into: GetValueInto,
+    pub(crate) get_property_value_into: GetValueInto,
+    pub(crate) get_field_info: GetFieldValue,
+    pub(crate) get_property_info: GetFieldValue,
+
+    pub(crate) get_field_accessor: GetAccessor,
+    pub(crate) get_property_accessor: GetAccessor,
+    pub(crate) get_accessor_info: unsafe extern "system" fn(*const c_void, *mut *const c_void, *mut i32) -> i32,
+    pub(crate) accessor_get: GetFieldValue,
+    pub(crate) accessor_get_into: GetValueInto,
+    pub(crate) accessor_set: SetFieldValue,
+
+    pub(crate) get_static_field_value: GetFieldValue,
+    pub(crate) set_static_field_value: SetFieldValue,
+    pub(crate) get_static_property_value: GetFieldValue,
+    pub(crate) set_static_property_value: SetFieldValue,
+    pub(crate) get_static_field_info: GetFieldValue,
+    pub(crate) get_static_property_info: GetFieldValue,
 
     pub(crate) runtime_invoke: Invoke,
+    pub(crate) runtime_invoke_reflection: Invoke,
+    pub(crate) runtime_invoke_batch: unsafe extern "system" fn(
+        i32,
+        *const *const c_void,
+        *const *const c_void,
+        *const *const c_void,
+        *mut i32,
+        *mut i32,
+    ) -> i32,
 }
 
 impl RuntimeLibrary {
     #[allow(clippy::missing_transmute_annotations)]
-    pub fn new(host: &Hostfxr) -> Self {
+    pub fn new(host: &Hostfxr) -> Result<Self> {
         unsafe {
-            RuntimeLibrary {
+            Ok(RuntimeLibrary {
                 ping: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "Ping",
                     "Host+PingDelegate, Runtime",
-                )),
+                )?),
                 destroy: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "Destroy",
                     "Host+DestroyDelegate, Runtime",
-                )),
+                )?),
                 free: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "Free",
                     "Host+FreeDelegate, Runtime",
-                )),
+                )?),
 
                 create_scope: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "CreateScope",
                     "Host+CreateScopeDelegate, Runtime",
-                )),
+                )?),
                 unload_scope: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "Unload",
                     "Host+UnloadDelegate, Runtime",
-                )),
+                )?),
                 load_from_path: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "LoadFromPath",
                     "Host+LoadFromPathDelegate, Runtime",
-                )),
+                )?),
+                load_from_bytes: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "LoadFromBytes",
+                    "Host+LoadFromBytesDelegate, Runtime",
+                )?),
+
+                compile: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "Compile",
+                    "Host+CompileDelegate, Runtime",
+                )?),
 
                 get_class: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "GetClass",
                     "Host+GetClassDelegate, Runtime",
-                )),
+                )?),
 
                 new: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "New",
                     "Host+NewDelegate, Runtime",
-                )),
+                )?),
+                new_with_args: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "NewWithArgs",
+                    "Host+NewWithArgsDelegate, Runtime",
+                )?),
+                get_constructor_info: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetConstructorInfo",
+                    "Host+GetConstructorInfoDelegate, Runtime",
+                )?),
                 is_assignable_from: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "IsAssignableFrom",
                     "Host+IsAssignableFromDelegate, Runtime",
-                )),
+                )?),
+                has_attribute: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "HasAttribute",
+                    "Host+HasAttributeDelegate, Runtime",
+                )?),
+                get_types: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetTypes",
+                    "Host+GetTypesDelegate, Runtime",
+                )?),
+                get_type_info: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetTypeInfo",
+                    "Host+GetTypeInfoDelegate, Runtime",
+                )?),
+                describe_assembly: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "DescribeAssembly",
+                    "Host+DescribeAssemblyDelegate, Runtime",
+                )?),
                 get_method: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "GetMethod",
                     "Host+GetMethodDelegate, Runtime",
-                )),
+                )?),
+                get_method_with_signature: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetMethodWithSignature",
+                    "Host+GetMethodWithSignatureDelegate, Runtime",
+                )?),
+                get_method_info: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetMethodInfo",
+                    "Host+GetMethodInfoDelegate, Runtime",
+                )?),
                 get_meta_data: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "GetMetaData",
                     "Host+GetMetaDataDelegate, Runtime",
-                )),
+                )?),
                 set_field_value: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "SetFieldValue",
                     "Host+SetFieldValueDelegate, Runtime",
-                )),
+                )?),
                 get_field_value: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "GetFieldValue",
                     "Host+GetFieldValueDelegate, Runtime",
-                )),
+                )?),
                 set_property_value: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "SetPropertyValue",
                     "Host+SetPropertyValueDelegate, Runtime",
-                )),
+                )?),
                 get_property_value: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "GetPropertyValue",
                     "Host+GetPropertyValueDelegate, Runtime",
-                )),
+                )?),
+                get_field_value_into: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetFieldValueInto",
+                    "Host+GetFieldValueIntoDelegate, Runtime",
+                )?),
+                get_property_value_into: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetPropertyValueInto",
+                    "Host+GetPropertyValueIntoDelegate, Runtime",
+                )?),
+                get_field_info: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetFieldInfo",
+                    "Host+GetFieldInfoDelegate, Runtime",
+                )?),
+                get_property_info: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetPropertyInfo",
+                    "Host+GetPropertyInfoDelegate, Runtime",
+                )?),
+                get_field_accessor: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetFieldAccessor",
+                    "Host+GetFieldAccessorDelegate, Runtime",
+                )?),
+                get_property_accessor: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetPropertyAccessor",
+                    "Host+GetPropertyAccessorDelegate, Runtime",
+                )?),
+                get_accessor_info: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetAccessorInfo",
+                    "Host+GetAccessorInfoDelegate, Runtime",
+                )?),
+                accessor_get: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "AccessorGet",
+                    "Host+AccessorGetDelegate, Runtime",
+                )?),
+                accessor_get_into: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "AccessorGetInto",
+                    "Host+AccessorGetIntoDelegate, Runtime",
+                )?),
+                accessor_set: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "AccessorSet",
+                    "Host+AccessorSetDelegate, Runtime",
+                )?),
+                get_static_field_value: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetStaticFieldValue",
+                    "Host+GetStaticFieldValueDelegate, Runtime",
+                )?),
+                set_static_field_value: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "SetStaticFieldValue",
+                    "Host+SetStaticFieldValueDelegate, Runtime",
+                )?),
+                get_static_property_value: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetStaticPropertyValue",
+                    "Host+GetStaticPropertyValueDelegate, Runtime",
+                )?),
+                set_static_property_value: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "SetStaticPropertyValue",
+                    "Host+SetStaticPropertyValueDelegate, Runtime",
+                )?),
+                get_static_field_info: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetStaticFieldInfo",
+                    "Host+GetStaticFieldInfoDelegate, Runtime",
+                )?),
+                get_static_property_info: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "GetStaticPropertyInfo",
+                    "Host+GetStaticPropertyInfoDelegate, Runtime",
+                )?),
 
                 runtime_invoke: std::mem::transmute(host.get_function_with_delegate(
                     "Host, Runtime",
                     "RuntimeInvoke",
                     "Host+RuntimeInvokeDelegate, Runtime",
-                )),
-            }
+                )?),
+                runtime_invoke_reflection: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "RuntimeInvokeReflection",
+                    "Host+RuntimeInvokeReflectionDelegate, Runtime",
+                )?),
+                runtime_invoke_batch: std::mem::transmute(host.get_function_with_delegate(
+                    "Host, Runtime",
+                    "RuntimeInvokeBatch",
+                    "Host+RuntimeInvokeBatchDelegate, Runtime",
+                )?),
+            })
         }
     }
 
@@ -494,89 +1600,393 @@ impl RuntimeLibrary {
         }
     }
 
-    pub fn load_from_path(&self, scope: &Scope, path: impl AsRef<Path>) -> Result<Option<Assembly>> {
-        let mut path = path.as_ref().display().to_string();
-        if !path.ends_with('\0') {
-            path.push('\0');
+    pub fn load_from_path(&self, scope: &Scope, path: impl AsRef<Path>) -> Result<Option<Assembly>> {
+        let mut path = path.as_ref().display().to_string();
+        if !path.ends_with('\0') {
+            path.push('\0');
+        }
+
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe { (self.load_from_path)(scope.as_ptr(), path.as_ptr().cast(), &raw mut out, &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+
+        Ok(if out.is_null() {
+            None
+        } else {
+            Some(Assembly::new(out))
+        })
+    }
+
+    pub fn load_from_bytes(&self, scope: &Scope, bytes: &[u8]) -> Result<Option<Assembly>> {
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe {
+            (self.load_from_bytes)(
+                scope.as_ptr(),
+                bytes.as_ptr(),
+                bytes.len() as i32,
+                &raw mut out,
+                &raw mut err,
+            )
+        };
+        if err > 0 { return Err(Error::from(err)); }
+
+        Ok(if out.is_null() {
+            None
+        } else {
+            Some(Assembly::new(out))
+        })
+    }
+
+    pub fn compile(
+        &self,
+        name: impl std::fmt::Display,
+        sources: &[PathBuf],
+        references: &[PathBuf],
+        project: &Project,
+        debug: bool,
+    ) -> Result<Compilation> {
+        let mut request = serde_json::json!({
+            "AssemblyName": name.to_string(),
+            "Sources": sources.iter().map(|v| v.display().to_string()).collect::<Vec<_>>(),
+            "References": references.iter().map(|v| v.display().to_string()).collect::<Vec<_>>(),
+            "Debug": debug,
+            "Nullable": project.nullable,
+            "LangVersion": project.lang_version,
+            "Defines": project.defines,
+        })
+        .to_string();
+        request.push('\0');
+
+        let mut bytes: *const c_void = std::ptr::null();
+        let mut length: i32 = 0;
+        let mut diagnostics: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe {
+            (self.compile)(
+                request.as_ptr().cast(),
+                &raw mut bytes,
+                &raw mut length,
+                &raw mut diagnostics,
+                &raw mut err,
+            )
+        };
+        if err > 0 { return Err(Error::from(err)); }
+
+        let mut compilation = Compilation::default();
+        if !diagnostics.is_null() {
+            let payload = unsafe { CStr::from_ptr(diagnostics.cast()) };
+            let value = serde_json::from_str(&payload.to_string_lossy());
+            unsafe { (self.free)(diagnostics) };
+            compilation.diagnostics = value?;
+        }
+
+        if !bytes.is_null() {
+            let image = unsafe { std::slice::from_raw_parts(bytes.cast::<u8>(), length as usize) };
+            compilation.assembly = Some(image.to_vec());
+            unsafe { (self.free)(bytes) };
+        }
+
+        Ok(compilation)
+    }
+
+    pub fn get_class(&self, assembly: &Assembly, name: impl std::fmt::Display) -> Result<Option<Class>> {
+        let mut name = name.to_string();
+        if !name.starts_with('\0') {
+            name.push('\0');
+        }
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe { (self.get_class)(assembly.as_ptr(), name.as_ptr().cast(), &raw mut out, &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+
+        Ok(if out.is_null() {
+            None
+        } else {
+            Some(Class::new(out, self.destroy))
+        })
+    }
+
+    pub fn new_object(&self, class: &Class) -> Result<Option<Object>> {
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe { (self.new)(class.as_ptr(), &raw mut out, &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+
+        Ok(if out.is_null() {
+            None
+        } else {
+            Some(self.wrap_object(out))
+        })
+    }
+
+    /// Create an instance with a constructor selected by `parameters` types, or by the number of `args` when `None`
+    pub fn new_object_with(
+        &self,
+        class: &Class,
+        parameters: Option<&[String]>,
+        args: &[*const c_void],
+    ) -> Result<Option<Object>> {
+        let parameters = constructor_parameters(parameters)?;
+
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe {
+            (self.new_with_args)(
+                class.as_ptr(),
+                parameters
+                    .as_ref()
+                    .map(|v| v.as_ptr().cast())
+                    .unwrap_or(std::ptr::null()),
+                args.len() as i32,
+                args.as_ptr(),
+                &raw mut out,
+                &raw mut err,
+            )
+        };
+        if err > 0 { return Err(Error::from(err)); }
+
+        Ok(if out.is_null() {
+            None
+        } else {
+            Some(self.wrap_object(out))
+        })
+    }
+
+    /// Describe the constructor [`RuntimeLibrary::new_object_with`] selects for the same `parameters` and argument count
+    pub fn get_constructor_info(
+        &self,
+        class: &Class,
+        parameters: Option<&[String]>,
+        arg_count: usize,
+    ) -> Result<MethodInfo> {
+        let parameters = constructor_parameters(parameters)?;
+
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe {
+            (self.get_constructor_info)(
+                class.as_ptr(),
+                parameters
+                    .as_ref()
+                    .map(|v| v.as_ptr().cast())
+                    .unwrap_or(std::ptr::null()),
+                arg_count as i32,
+                &raw mut out,
+                &raw mut err,
+            )
+        };
+        if err > 0 { return Err(Error::from(err)); }
+        if out.is_null() {
+            return Err(Error::UnknownManaged);
+        }
+
+        let payload = unsafe { CStr::from_ptr(out.cast()) };
+        let value = serde_json::from_str(&payload.to_string_lossy());
+        unsafe { (self.free)(out) };
+        Ok(value?)
+    }
+
+    /// Describe a resolved method so its arguments can be checked, the handle is released on failure
+    fn wrap_method(&self, inner: *const c_void) -> Result<Method> {
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe { (self.get_method_info)(inner, &raw mut out, &raw mut err) };
+
+        let info = if err > 0 {
+            Err(Error::from(err))
+        } else if out.is_null() {
+            Err(Error::UnknownManaged)
+        } else {
+            let payload = unsafe { CStr::from_ptr(out.cast()) };
+            let value = serde_json::from_str::<MethodInfo>(&payload.to_string_lossy());
+            unsafe { (self.free)(out) };
+            value.map_err(Error::from)
+        };
+
+        match info {
+            Ok(info) => Ok(Method::new(inner, self.destroy, info)),
+            Err(err) => {
+                unsafe { (self.destroy)(inner) };
+                Err(err)
+            }
+        }
+    }
+
+    /// Resolve a field or property accessor with `get`, either `get_field_accessor` or `get_property_accessor`
+    pub fn get_accessor(&self, get: GetAccessor, class: &Class, name: &str) -> Result<Accessor> {
+        let name = to_cstring(name);
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe { get(class.as_ptr(), name.as_ptr().cast(), &raw mut out, &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+        if out.is_null() { return Err(Error::UnknownManaged); }
+
+        // Described so values can be checked before they are written, the handle is released on failure
+        let mut info: *const c_void = std::ptr::null();
+        unsafe { (self.get_accessor_info)(out, &raw mut info, &raw mut err) };
+        let info = if err > 0 {
+            Err(Error::from(err))
+        } else if info.is_null() {
+            Err(Error::UnknownManaged)
+        } else {
+            let payload = unsafe { CStr::from_ptr(info.cast()) };
+            let value = serde_json::from_str::<ParameterInfo>(&payload.to_string_lossy());
+            unsafe { (self.free)(info) };
+            value.map_err(Error::from)
+        };
+
+        match info {
+            Ok(info) => Ok(Accessor {
+                inner: out,
+                info,
+                get: self.accessor_get,
+                get_into: self.accessor_get_into,
+                set: self.accessor_set,
+                destroy: self.destroy,
+                free: self.free,
+            }),
+            Err(err) => {
+                unsafe { (self.destroy)(out) };
+                Err(err)
+            }
+        }
+    }
+
+    fn wrap_object(&self, inner: *const c_void) -> Object {
+        Object {
+            inner,
+            get_field_value: self.get_field_value,
+            set_field_value: self.set_field_value,
+            get_property_value: self.get_property_value,
+            set_property_value: self.set_property_value,
+            get_field_value_into: self.get_field_value_into,
+            get_property_value_into: self.get_property_value_into,
+            get_field_info: self.get_field_info,
+            get_property_info: self.get_property_info,
+            destroy: self.destroy,
+            free: self.free,
+        }
+    }
+
+    pub fn is_assignable_from(&self, base: &Class, target: &Class) -> Result<bool> {
+        let mut out: i32 = 0;
+        let mut err: i32 = -1;
+        unsafe { (self.is_assignable_from)(base.as_ptr(), target.as_ptr(), &raw mut out, &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+        Ok(out == 1)
+    }
+
+    pub fn has_attribute(&self, class: &Class, attribute: &Class) -> Result<bool> {
+        let mut out: i32 = 0;
+        let mut err: i32 = -1;
+        unsafe { (self.has_attribute)(class.as_ptr(), attribute.as_ptr(), &raw mut out, &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+        Ok(out == 1)
+    }
+
+    /// Full names of the classes in `assembly` that can be instantiated
+    pub fn get_types(&self, assembly: &Assembly) -> Result<Vec<String>> {
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe { (self.get_types)(assembly.as_ptr(), &raw mut out, &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+
+        if !out.is_null() {
+            let payload = unsafe { CStr::from_ptr(out.cast()) };
+            let value = serde_json::from_str(&payload.to_string_lossy());
+            unsafe { (self.free)(out) };
+            return Ok(value?);
         }
 
+        Ok(Vec::new())
+    }
+
+    pub fn get_type_info(&self, class: &Class) -> Result<TypeInfo> {
         let mut out: *const c_void = std::ptr::null();
         let mut err: i32 = -1;
-        unsafe { (self.load_from_path)(scope.as_ptr(), path.as_ptr().cast(), &raw mut out, &raw mut err) };
+        unsafe { (self.get_type_info)(class.as_ptr(), &raw mut out, &raw mut err) };
         if err > 0 { return Err(Error::from(err)); }
 
-        Ok(if out.is_null() {
-            None
-        } else {
-            Some(Assembly::new(out))
-        })
+        if !out.is_null() {
+            let payload = unsafe { CStr::from_ptr(out.cast()) };
+            let value = serde_json::from_str(&payload.to_string_lossy());
+            unsafe { (self.free)(out) };
+            return Ok(value?);
+        }
+
+        Ok(Default::default())
     }
 
-    pub fn get_class(&self, assembly: &Assembly, name: impl std::fmt::Display) -> Result<Option<Class>> {
-        let mut name = name.to_string();
-        if !name.starts_with('\0') {
-            name.push('\0');
-        }
+    pub fn describe_assembly(&self, assembly: &Assembly) -> Result<Vec<TypeInfo>> {
         let mut out: *const c_void = std::ptr::null();
         let mut err: i32 = -1;
-        unsafe { (self.get_class)(assembly.as_ptr(), name.as_ptr().cast(), &raw mut out, &raw mut err) };
+        unsafe { (self.describe_assembly)(assembly.as_ptr(), &raw mut out, &raw mut err) };
         if err > 0 { return Err(Error::from(err)); }
 
-        Ok(if out.is_null() {
-            None
-        } else {
-            Some(Class::new(out, self.destroy))
-        })
+        if !out.is_null() {
+            let payload = unsafe { CStr::from_ptr(out.cast()) };
+            let value = serde_json::from_str(&payload.to_string_lossy());
+            unsafe { (self.free)(out) };
+            return Ok(value?);
+        }
+
+        Ok(Vec::new())
     }
 
-    pub fn new_object(&self, class: &Class) -> Result<Option<Object>> {
+    pub fn get_method(
+        &self,
+        class: &Class,
+        name: impl std::fmt::Display,
+        args: i32,
+    ) -> Result<Option<Method>> {
+        let mut name = name.to_string();
+        if !name.starts_with('\0') {
+            name.push('\0');
+        }
         let mut out: *const c_void = std::ptr::null();
         let mut err: i32 = -1;
-        unsafe { (self.new)(class.as_ptr(), &raw mut out, &raw mut err) };
+        unsafe { (self.get_method)(class.as_ptr(), name.as_ptr().cast(), args, &raw mut out, &raw mut err) };
         if err > 0 { return Err(Error::from(err)); }
 
         Ok(if out.is_null() {
             None
         } else {
-            Some(Object {
-                inner: out,
-                get_field_value: self.get_field_value,
-                set_field_value: self.set_field_value,
-                get_property_value: self.get_property_value,
-                set_property_value: self.set_property_value,
-                destroy: self.destroy,
-                free: self.free,
-            })
+            Some(self.wrap_method(out)?)
         })
     }
 
-    pub fn is_assignable_from(&self, base: &Class, target: &Class) -> Result<bool> {
-        let mut out: i32 = 0;
-        let mut err: i32 = -1;
-        unsafe { (self.is_assignable_from)(base.as_ptr(), target.as_ptr(), &raw mut out, &raw mut err) };
-        if err > 0 { return Err(Error::from(err)); }
-        Ok(out == 1)
-    }
-
-    pub fn get_method(
+    pub fn get_method_with_signature(
         &self,
         class: &Class,
         name: impl std::fmt::Display,
-        args: i32,
+        parameters: &[String],
     ) -> Result<Option<Method>> {
         let mut name = name.to_string();
-        if !name.starts_with('\0') {
+        if !name.ends_with('\0') {
             name.push('\0');
         }
+        let mut parameters = serde_json::to_string(parameters)?;
+        parameters.push('\0');
+
         let mut out: *const c_void = std::ptr::null();
         let mut err: i32 = -1;
-        unsafe { (self.get_method)(class.as_ptr(), name.as_ptr().cast(), args, &raw mut out, &raw mut err) };
+        unsafe {
+            (self.get_method_with_signature)(
+                class.as_ptr(),
+                name.as_ptr().cast(),
+                parameters.as_ptr().cast(),
+                &raw mut out,
+                &raw mut err,
+            )
+        };
         if err > 0 { return Err(Error::from(err)); }
 
         Ok(if out.is_null() {
             None
         } else {
-            Some(Method::new(out, self.destroy))
+            Some(self.wrap_method(out)?)
         })
     }
 
@@ -604,17 +2014,16 @@ impl RuntimeLibrary {
         name: impl AsRef<str>,
         value: impl ManagedParam,
     ) -> Result<()> {
-        let mut name = name.as_ref().to_string();
-        if !name.ends_with('\0') {
-            name.push('\0');
-        }
+        let name = to_cstring(name.as_ref());
+        let value = value.into_managed_param()?;
+        check_value(&describe_value(self.get_field_info, self.free, instance.as_ptr(), &name)?, &value)?;
 
         let mut err: i32 = -1;
         unsafe {
             (self.set_field_value)(
                 instance.as_ptr(),
                 name.as_ptr().cast(),
-                value.into_managed_param(),
+                value.as_ptr(),
                 &raw mut err,
             )
         };
@@ -622,6 +2031,56 @@ impl RuntimeLibrary {
         Ok(())
     }
 
+    /// Read a static field or property with `get`, either `get_static_field_value` or `get_static_property_value`
+    pub fn get_static_value<A: DeserializeOwned>(
+        &self,
+        get: GetFieldValue,
+        class: &Class,
+        name: impl AsRef<str>,
+    ) -> Result<Option<A>> {
+        let mut name = name.as_ref().to_string();
+        if !name.ends_with('\0') {
+            name.push('\0');
+        }
+
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe { get(class.as_ptr(), name.as_ptr().cast(), &raw mut out, &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+
+        if out.is_null() {
+            return Ok(None);
+        }
+
+        let payload = unsafe { CStr::from_ptr(out.cast()) };
+        let value = serde_json::from_str(&payload.to_string_lossy());
+        unsafe { (self.free)(out) };
+
+        Ok(Some(value?))
+    }
+
+    /// Write a static field or property with `set`, either `set_static_field_value` or `set_static_property_value`
+    ///
+    /// The value is first checked against the type described by `describe`, the matching
+    /// `get_static_field_info` or `get_static_property_info`.
+    pub fn set_static_value(
+        &self,
+        describe: GetFieldValue,
+        set: SetFieldValue,
+        class: &Class,
+        name: impl AsRef<str>,
+        value: impl ManagedParam,
+    ) -> Result<()> {
+        let name = to_cstring(name.as_ref());
+        let value = value.into_managed_param()?;
+        check_value(&describe_value(describe, self.free, class.as_ptr(), &name)?, &value)?;
+
+        let mut err: i32 = -1;
+        unsafe { set(class.as_ptr(), name.as_ptr().cast(), value.as_ptr(), &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+        Ok(())
+    }
+
     pub fn invoke(&self, method: &Method, instance: Option<&Object>, args: &[*const c_void]) -> Result<()> {
         let mut err: i32 = -1;
         unsafe {
@@ -639,6 +2098,14 @@ impl RuntimeLibrary {
     }
 }
 
+/// Output of an in-process Roslyn compilation
+#[derive(Default)]
+pub struct Compilation {
+    /// Assembly image, `None` when the compilation failed
+    pub assembly: Option<Vec<u8>>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
 pub trait Wrapper {
     fn as_ptr(&self) -> *const c_void;
 }
@@ -701,11 +2168,94 @@ impl Drop for Class {
 pub struct Method {
     inner: *const c_void,
     destroy: Destroy,
+    /// Signature of the method, described when it is resolved
+    info: MethodInfo,
 }
 impl Method {
-    fn new(inner: *const c_void, destroy: Destroy) -> Self {
-        Self { inner, destroy }
+    fn new(inner: *const c_void, destroy: Destroy, info: MethodInfo) -> Self {
+        Self { inner, destroy, info }
+    }
+
+    pub fn info(&self) -> &MethodInfo {
+        &self.info
+    }
+
+    /// Verify `args` match the parameters of the method so mismatched values aren't read as the wrong type
+    pub fn check(&self, args: &ManagedArgs) -> Result<()> {
+        check_arguments(&self.info, args)
+    }
+}
+
+fn check_arguments(info: &MethodInfo, args: &ManagedArgs) -> Result<()> {
+    if args.len() != info.parameters.len() {
+        return Err(Error::ArgumentCountMismatch {
+            method: info.signature(),
+            expected: info.parameters.len(),
+            found: args.len(),
+        });
     }
+
+    for (position, (kind, parameter)) in args.kinds().zip(&info.parameters).enumerate() {
+        if !kind.accepts(parameter) {
+            return Err(Error::ArgumentTypeMismatch {
+                method: info.signature(),
+                position,
+                expected: expected_type(parameter),
+                found: kind.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Verify `value` matches the field or property described by `info` before it is written
+fn check_value(info: &ParameterInfo, value: &ManagedValue) -> Result<()> {
+    if value.kind().accepts(info) {
+        return Ok(());
+    }
+    Err(Error::ArgumentTypeMismatch {
+        method: info.name.clone().unwrap_or_default(),
+        position: 0,
+        expected: expected_type(info),
+        found: value.kind().to_string(),
+    })
+}
+
+fn expected_type(parameter: &ParameterInfo) -> String {
+    let r#type = &parameter.r#type;
+    if parameter.is_by_ref_like {
+        return format!("{type}, a ref struct which can't be passed from rust, take an array instead");
+    }
+    match (parameter.size, parameter.element_size) {
+        (0, 0) => r#type.clone(),
+        (0, size) => format!("{type} ({size} byte elements)"),
+        (size, _) => format!("{type} ({size} bytes)"),
+    }
+}
+
+/// Describe the type of the field or property `name` of `target` with `describe`, e.g. `get_field_info`
+fn describe_value(describe: GetFieldValue, free: Destroy, target: *const c_void, name: &CStr) -> Result<ParameterInfo> {
+    let mut out: *const c_void = std::ptr::null();
+    let mut err: i32 = -1;
+    unsafe { describe(target, name.as_ptr().cast(), &raw mut out, &raw mut err) };
+    if err > 0 { return Err(Error::from(err)); }
+    if out.is_null() { return Err(Error::UnknownManaged); }
+
+    let payload = unsafe { CStr::from_ptr(out.cast()) };
+    let value = serde_json::from_str::<ParameterInfo>(&payload.to_string_lossy());
+    unsafe { free(out) };
+    Ok(value?)
+}
+
+/// Parameter types selecting a constructor as a null terminated JSON array
+fn constructor_parameters(parameters: Option<&[String]>) -> Result<Option<String>> {
+    parameters
+        .map(|parameters| -> Result<String> {
+            let mut parameters = serde_json::to_string(parameters)?;
+            parameters.push('\0');
+            Ok(parameters)
+        })
+        .transpose()
 }
 impl Wrapper for Method {
     fn as_ptr(&self) -> *const c_void {
@@ -718,27 +2268,140 @@ impl Drop for Method {
     }
 }
 
+/// A field or property resolved once, read and written through delegates compiled by the runtime
+pub struct Accessor {
+    inner: *const c_void,
+    info: ParameterInfo,
+    get: GetFieldValue,
+    get_into: GetValueInto,
+    set: SetFieldValue,
+    destroy: Destroy,
+    free: Destroy,
+}
+impl Accessor {
+    pub fn name(&self) -> &str {
+        self.info.name.as_deref().unwrap_or_default()
+    }
+
+    /// Type and marshalled size of the value, described like a parameter
+    pub fn info(&self) -> &ParameterInfo {
+        &self.info
+    }
+
+    /// Read the value from `instance` as JSON
+    pub fn get<A: DeserializeOwned>(&self, instance: &Object) -> Result<Option<A>> {
+        let mut out: *const c_void = std::ptr::null();
+        let mut err: i32 = -1;
+        unsafe { (self.get)(self.inner, instance.as_ptr(), &raw mut out, &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+
+        if out.is_null() {
+            return Ok(None);
+        }
+
+        let payload = unsafe { CStr::from_ptr(out.cast()) };
+        let value = serde_json::from_str(&payload.to_string_lossy());
+        unsafe { (self.free)(out) };
+
+        Ok(Some(value?))
+    }
+
+    /// Copy the value from `instance` straight into `value`, values that aren't blittable are read as JSON
+    ///
+    /// See [`Object::get_field_value_into`]
+    pub fn get_into<T: Pod + DeserializeOwned>(&self, instance: &Object, value: &mut T) -> Result<()> {
+        let buffer = bytemuck::bytes_of_mut(value);
+        let mut err: i32 = -1;
+        unsafe {
+            (self.get_into)(
+                self.inner,
+                instance.as_ptr(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len() as i32,
+                &raw mut err,
+            )
+        };
+        if err > 0 {
+            return match Error::from(err) {
+                Error::NotBlittable => {
+                    if let Some(v) = self.get(instance)? {
+                        *value = v;
+                    }
+                    Ok(())
+                }
+                err => Err(err),
+            };
+        }
+        Ok(())
+    }
+
+    /// Write `value` to `instance`, blittable values are copied without boxing
+    pub fn set(&self, instance: &Object, value: impl ManagedParam) -> Result<()> {
+        let value = value.into_managed_param()?;
+        check_value(&self.info, &value)?;
+
+        let mut err: i32 = -1;
+        unsafe { (self.set)(self.inner, instance.as_ptr(), value.as_ptr(), &raw mut err) };
+        if err > 0 { return Err(Error::from(err)); }
+        Ok(())
+    }
+}
+impl Wrapper for Accessor {
+    fn as_ptr(&self) -> *const c_void {
+        self.inner
+    }
+}
+impl Drop for Accessor {
+    fn drop(&mut self) {
+        unsafe { (self.destroy)(self.inner) };
+    }
+}
+
+/// Cached instance field of a class, see [`Runtime::field_accessor`]
+#[derive(Clone)]
+pub struct FieldAccessor(Rc<Accessor>);
+impl Deref for FieldAccessor {
+    type Target = Accessor;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Cached instance property of a class, see [`Runtime::property_accessor`]
+#[derive(Clone)]
+pub struct PropertyAccessor(Rc<Accessor>);
+impl Deref for PropertyAccessor {
+    type Target = Accessor;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
 pub struct Object {
     inner: *const c_void,
     get_field_value: GetFieldValue,
     set_field_value: SetFieldValue,
     get_property_value: GetFieldValue,
     set_property_value: SetFieldValue,
+    get_field_value_into: GetValueInto,
+    get_property_value_into: GetValueInto,
+    get_field_info: GetFieldValue,
+    get_property_info: GetFieldValue,
     destroy: Destroy,
     free: Destroy,
 }
 unsafe impl Send for Object {}
 unsafe impl Sync for Object {}
 impl Object {
+    /// Write the field `name`, `value` is checked against the field type first like method arguments
     pub fn set_field_value(&self, name: impl AsRef<str>, value: impl ManagedParam) -> Result<()> {
-        let mut name = name.as_ref().to_string();
-        if !name.ends_with('\0') {
-            name.push('\0');
-        }
+        let name = to_cstring(name.as_ref());
+        let value = value.into_managed_param()?;
+        check_value(&describe_value(self.get_field_info, self.free, self.inner, &name)?, &value)?;
 
         let mut err: i32 = -1;
         unsafe {
-            (self.set_field_value)(self.inner, name.as_ptr().cast(), value.into_managed_param(), &raw mut err)
+            (self.set_field_value)(self.inner, name.as_ptr().cast(), value.as_ptr(), &raw mut err)
         };
         if err > 0 { return Err(Error::from(err)); }
         Ok(())
@@ -768,15 +2431,15 @@ impl Object {
         Ok(Some(value))
     }
 
+    /// Write the property `name`, `value` is checked against the property type first like method arguments
     pub fn set_property_value(&self, name: impl AsRef<str>, value: impl ManagedParam) -> Result<()> {
-        let mut name = name.as_ref().to_string();
-        if !name.ends_with('\0') {
-            name.push('\0');
-        }
+        let name = to_cstring(name.as_ref());
+        let value = value.into_managed_param()?;
+        check_value(&describe_value(self.get_property_info, self.free, self.inner, &name)?, &value)?;
 
         let mut err: i32 = -1;
         unsafe {
-            (self.set_property_value)(self.inner, name.as_ptr().cast(), value.into_managed_param(), &raw mut err)
+            (self.set_property_value)(self.inner, name.as_ptr().cast(), value.as_ptr(), &raw mut err)
         };
         if err > 0 { return Err(Error::from(err)); }
         Ok(())
@@ -805,6 +2468,71 @@ impl Object {
 
         Ok(Some(value))
     }
+
+    /// Read a field by copying it straight into `value`, skipping JSON
+    ///
+    /// Only blittable managed values, e.g. primitives and structs of them, are copied. Anything else,
+    /// like a struct containing a `bool` or a reference, is read with [`Object::get_field_value`] instead.
+    /// Only the size of the managed value is checked, its layout must match `T`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use bevy_cs_managed::{ManagedStruct, Script};
+    /// #[repr(C)]
+    /// #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable, ManagedStruct)]
+    /// struct Vector3 { x: f32, y: f32, z: f32 }
+    ///
+    /// # fn read(script: &Script) -> bevy_cs_managed::Result<()> {
+    /// let mut position = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+    /// script.get_field_value_into("Position", &mut position)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_field_value_into<T: Pod + DeserializeOwned>(&self, name: impl AsRef<str>, value: &mut T) -> Result<()> {
+        match self.read_into(self.get_field_value_into, name.as_ref(), value) {
+            Err(Error::NotBlittable) => {
+                if let Some(v) = self.get_field_value(name)? {
+                    *value = v;
+                }
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Read a property by copying it straight into `value`, skipping JSON
+    ///
+    /// See [`Object::get_field_value_into`], values that aren't blittable are read with
+    /// [`Object::get_property_value`].
+    pub fn get_property_value_into<T: Pod + DeserializeOwned>(&self, name: impl AsRef<str>, value: &mut T) -> Result<()> {
+        match self.read_into(self.get_property_value_into, name.as_ref(), value) {
+            Err(Error::NotBlittable) => {
+                if let Some(v) = self.get_property_value(name)? {
+                    *value = v;
+                }
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    fn read_into<T: Pod>(&self, get: GetValueInto, name: &str, value: &mut T) -> Result<()> {
+        let name = to_cstring(name);
+        let buffer = bytemuck::bytes_of_mut(value);
+
+        let mut err: i32 = -1;
+        unsafe {
+            get(
+                self.inner,
+                name.as_ptr().cast(),
+                buffer.as_mut_ptr().cast(),
+                buffer.len() as i32,
+                &raw mut err,
+            )
+        };
+        if err > 0 { return Err(Error::from(err)); }
+        Ok(())
+    }
 }
 impl Wrapper for Object {
     fn as_ptr(&self) -> *const c_void {
@@ -841,3 +2569,147 @@ pub struct Property {
     pub can_read: bool,
     pub can_write: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parameter(r#type: &str, is_value_type: bool, size: usize) -> ParameterInfo {
+        ParameterInfo {
+            name: Some("value".into()),
+            r#type: r#type.into(),
+            is_value_type,
+            size,
+            element_size: 0,
+            is_by_ref_like: false,
+            is_out: false,
+            is_optional: false,
+        }
+    }
+
+    fn kind(value: impl ManagedParam) -> ArgumentKind {
+        value.into_managed_param().unwrap().kind()
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Vector3 {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+    impl ManagedType for Vector3 {
+        const TYPE_NAME: &'static str = "Engine.Vector3";
+    }
+
+    #[test]
+    fn primitives() {
+        let int = kind(&5i32);
+        assert_eq!(int, ArgumentKind::Value { type_name: "System.Int32", size: 4 });
+        assert!(int.accepts(&parameter("System.Int32", true, 0)));
+        assert!(int.accepts(&parameter("System.Int32&", true, 0)));
+        assert!(int.accepts(&parameter("System.Nullable<System.Int32>", true, 0)));
+        assert!(!int.accepts(&parameter("System.Int64", true, 0)));
+        assert!(!int.accepts(&parameter("System.Single", true, 0)));
+        assert!(!int.accepts(&parameter("System.Object", false, 0)));
+
+        let flag = kind(true);
+        assert!(flag.accepts(&parameter("System.Boolean", true, 0)));
+        assert!(!flag.accepts(&parameter("System.Byte", true, 0)));
+    }
+
+    #[test]
+    fn strings() {
+        for string in [kind("name"), kind(String::from("name")), kind(c"name")] {
+            assert_eq!(string, ArgumentKind::String);
+            assert!(string.accepts(&parameter("System.String", false, 0)));
+            assert!(!string.accepts(&parameter("System.Object", false, 0)));
+            assert!(!string.accepts(&parameter("System.Char", true, 0)));
+        }
+    }
+
+    #[test]
+    fn arrays() {
+        let values = [1.0f32, 2.0];
+        for array in [kind(values.as_slice()), kind(values.to_vec())] {
+            assert_eq!(array, ArgumentKind::Array { type_name: "System.Single", size: 4 });
+            assert!(array.accepts(&parameter("System.Single[]", false, 0)));
+            assert!(!array.accepts(&parameter("System.Single", true, 0)));
+            assert!(!array.accepts(&parameter("System.Double[]", false, 0)));
+            assert!(!array.accepts(&parameter("System.Single[][]", false, 0)));
+        }
+
+        let vectors = [Vector3 { x: 0.0, y: 1.0, z: 2.0 }];
+        let array = kind(vectors.as_slice());
+        assert!(array.accepts(&parameter("Engine.Vector3[]", false, 0)));
+        assert!(array.accepts(&ParameterInfo { element_size: 12, ..parameter("Engine.Vector3[]", false, 0) }));
+        assert!(!array.accepts(&parameter("Engine.Vector2[]", false, 0)));
+        // Same element name with another layout
+        assert!(!array.accepts(&ParameterInfo { element_size: 16, ..parameter("Engine.Vector3[]", false, 0) }));
+    }
+
+    #[test]
+    fn spans_are_rejected() {
+        let values = [1.0f32, 2.0];
+        let span = ParameterInfo { is_by_ref_like: true, ..parameter("System.ReadOnlySpan<System.Single>", true, 0) };
+        assert!(!kind(values.as_slice()).accepts(&span));
+        assert!(!ArgumentKind::Object.accepts(&span));
+        assert!(!ArgumentKind::Null.accepts(&span));
+
+        let method = MethodInfo {
+            name: "Sum".into(),
+            return_type: "System.Single".into(),
+            is_static: false,
+            is_public: true,
+            is_abstract: false,
+            is_virtual: false,
+            generic_parameters: Vec::new(),
+            parameters: vec![span],
+            attributes: Vec::new(),
+        };
+        let mut args = ManagedArgs::new();
+        args.push(values.as_slice()).unwrap();
+        let message = check_arguments(&method, &args).unwrap_err().to_string();
+        assert!(message.contains("a ref struct which can't be passed from rust"), "{message}");
+    }
+
+    #[test]
+    fn structs() {
+        let vector = kind(&Vector3 { x: 0.0, y: 1.0, z: 2.0 });
+        assert_eq!(vector, ArgumentKind::Value { type_name: "Engine.Vector3", size: 12 });
+        assert!(vector.accepts(&parameter("Engine.Vector3", true, 12)));
+        assert!(vector.accepts(&parameter("Engine.Vector3&", true, 12)));
+        assert!(vector.accepts(&parameter("System.Nullable<Engine.Vector3>", true, 0)));
+        // Same name with another layout
+        assert!(!vector.accepts(&parameter("Engine.Vector3", true, 16)));
+        assert!(!vector.accepts(&parameter("Engine.Vector4", true, 12)));
+    }
+
+    #[test]
+    fn objects_and_null() {
+        assert!(ArgumentKind::Object.accepts(&parameter("Game.Player", false, 0)));
+        assert!(ArgumentKind::Object.accepts(&parameter("System.Object", false, 0)));
+        assert!(!ArgumentKind::Object.accepts(&parameter("Engine.Vector3", true, 12)));
+        assert!(!ArgumentKind::Object.accepts(&parameter("System.String", false, 0)));
+        assert!(!ArgumentKind::Object.accepts(&parameter("System.Single[]", false, 0)));
+        assert!(!ArgumentKind::Object.accepts(&parameter("Game.Player[]", false, 0)));
+
+        let null = kind(None::<&i32>);
+        assert_eq!(null, ArgumentKind::Null);
+        assert!(null.accepts(&parameter("System.String", false, 0)));
+        assert!(null.accepts(&parameter("System.Nullable<System.Int32>", true, 0)));
+        assert!(!null.accepts(&parameter("System.Int32", true, 0)));
+    }
+
+    #[test]
+    fn values_are_checked_before_writing() {
+        let speed = parameter("System.Single", true, 0);
+        assert!(check_value(&speed, &(&1.0f32).into_managed_param().unwrap()).is_ok());
+
+        let error = check_value(&speed, &(&1.0f64).into_managed_param().unwrap()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "argument 0 of `value` expected `System.Single` but found `System.Double (8 bytes)`"
+        );
+    }
+}
@@ -0,0 +1,39 @@
+using System;
+using Engine;
+
+public class Counter : Behaviour {
+    public int Count;
+    public Vector3 Position;
+    public readonly int Limit = 10;
+    public string Name = "";
+
+    public float Speed { get; set; } = 1;
+    public int Doubled => Count * 2;
+
+    public void Increment() {
+        Count++;
+    }
+
+    public void Add(int amount) {
+        Count += amount;
+    }
+
+    // Ref structs can't be passed from rust
+    public void AddAll(ReadOnlySpan<int> amounts) {
+        foreach (var amount in amounts) {
+            Count += amount;
+        }
+    }
+
+    public void Rename(string name) {
+        Name = name;
+    }
+
+    public void Move(Vector3 offset) {
+        Position = Position + offset;
+    }
+
+    public void Tick(float dt) {
+        Count++;
+    }
+}
@@ -0,0 +1,16 @@
+using System;
+using Engine;
+
+[Script]
+public class Faulty {
+    public float Total;
+
+    // Same name as `Counter.Add` with another parameter type
+    public void Add(float amount) {
+        Total += amount;
+    }
+
+    public void Tick(float dt) {
+        throw new InvalidOperationException("thrown by the Faulty fixture");
+    }
+}
@@ -0,0 +1,9 @@
+// Not a script, only reachable through static access
+public class Helper {
+    public static int Answer = 42;
+    public static int Calls;
+
+    public static void Call() {
+        Calls++;
+    }
+}
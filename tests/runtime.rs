@@ -0,0 +1,254 @@
+//! Boots the runtime headlessly in a `MinimalPlugins` app with the scripts in `tests/fixtures`
+//!
+//! The runtime can only be hosted once per process, so the cases share one app and run in order
+//! instead of through the default test harness. Skipped when no .NET SDK is installed since the
+//! fixtures can't be compiled.
+//!
+//! The fixtures and engine sources are copied under `target` first so the generated projects,
+//! solution and build output don't end up in the source tree.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy_cs_managed::{dotnet::discovery, CSharpPlugin, Error, ManagedStruct, Runtime, Script, ScriptingDisabled};
+use bytemuck::Zeroable;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, Zeroable, ManagedStruct)]
+struct Vector3 {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+type Case = (&'static str, fn(&mut App));
+
+const CASES: &[Case] = &[
+    ("register", register),
+    ("create", create),
+    ("invoke", invoke),
+    ("fields", fields),
+    ("properties", properties),
+    ("errors", errors),
+    ("systems", systems),
+];
+
+fn main() {
+    if discovery::installs().iter().all(|install| install.sdks.is_empty()) {
+        println!("skipping runtime tests: no .NET SDK installed");
+        return;
+    }
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugins(
+        CSharpPlugin::default()
+            .config(workspace().expect("failed to copy the fixtures"))
+            .auto_register(false)
+            .optional(true),
+    );
+    if let Some(disabled) = app.world().get_resource::<ScriptingDisabled>() {
+        eprintln!("the C# runtime failed to start: {}", disabled.error);
+        std::process::exit(1);
+    }
+
+    let mut failed = 0;
+    for (name, case) in CASES {
+        match catch_unwind(AssertUnwindSafe(|| case(&mut app))) {
+            Ok(()) => println!("test {name} ... ok"),
+            Err(_) => {
+                println!("test {name} ... FAILED");
+                failed += 1;
+            }
+        }
+    }
+
+    let status = if failed == 0 { "ok" } else { "FAILED" };
+    println!("\ntest result: {status}. {} passed; {failed} failed", CASES.len() - failed);
+    if failed > 0 {
+        std::process::exit(101);
+    }
+}
+
+/// Copy the fixtures and engine sources into a directory under `target`, returning its managed config
+fn workspace() -> std::io::Result<PathBuf> {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("runtime");
+    copy_sources(Path::new("assets/engine"), &dir.join("engine"))?;
+    copy_sources(Path::new("tests/fixtures"), &dir.join("scripts"))?;
+
+    let config = dir.join("managed.config.json");
+    let contents = serde_json::json!({
+        "solution": dir,
+        "engine": { "roots": [dir.join("engine")] },
+        "scripts": { "roots": [dir.join("scripts")] },
+    });
+    std::fs::write(&config, contents.to_string())?;
+    Ok(config)
+}
+
+/// Copy the C# sources in `from` into `to`, keeping the build output of earlier runs
+fn copy_sources(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let path = entry?.path();
+        let target = to.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_sources(&path, &target)?;
+        } else if path.extension().is_some_and(|ext| ext == "cs") {
+            std::fs::copy(&path, target)?;
+        }
+    }
+    Ok(())
+}
+
+fn register(app: &mut App) {
+    let mut runtime = app.world_mut().resource_mut::<Runtime>();
+    let mut names = runtime.register_all().unwrap();
+    names.sort();
+    assert_eq!(names, ["Counter", "Faulty"]);
+
+    // Already registered classes are skipped
+    assert!(runtime.register_all().unwrap().is_empty());
+}
+
+fn create(app: &mut App) {
+    let runtime = app.world().resource::<Runtime>();
+    runtime.create("Counter").unwrap();
+    runtime.create("Faulty").unwrap();
+    assert!(matches!(runtime.create("Helper"), Err(Error::ClassNotRegistered)));
+    assert!(matches!(runtime.create("Missing"), Err(Error::ClassNotRegistered)));
+}
+
+fn invoke(app: &mut App) {
+    let runtime = app.world().resource::<Runtime>();
+    let script = runtime.create("Counter").unwrap();
+
+    runtime.get_method(&script, "Increment", 0).unwrap().unwrap().invoke(()).unwrap();
+    runtime.get_method(&script, "Add", 1).unwrap().unwrap().invoke(&41).unwrap();
+    assert_eq!(script.get_field_value::<i32>("Count").unwrap(), Some(42));
+
+    let offset = Vector3 { x: 1.0, y: 2.0, z: 3.0 };
+    let r#move = runtime.get_method_typed::<Vector3>(&script, "Move").unwrap().unwrap();
+    r#move.invoke(&offset).unwrap();
+    r#move.invoke_with_reflection(&offset).unwrap();
+    assert_eq!(script.get_field_value::<Vector3>("Position").unwrap(), Some(Vector3 { x: 2.0, y: 4.0, z: 6.0 }));
+
+    assert!(runtime.get_method(&script, "Missing", 0).unwrap().is_none());
+
+    runtime.get_static_method("Helper", "Call", &[]).unwrap().unwrap().invoke(()).unwrap();
+    assert_eq!(runtime.get_static_field_value::<i32>("Helper", "Calls").unwrap(), Some(1));
+    assert_eq!(runtime.get_static_field_value::<i32>("Helper", "Answer").unwrap(), Some(42));
+    runtime.set_static_field_value("Helper", "Answer", &7).unwrap();
+    assert_eq!(runtime.get_static_field_value::<i32>("Helper", "Answer").unwrap(), Some(7));
+}
+
+fn fields(app: &mut App) {
+    let runtime = app.world().resource::<Runtime>();
+    let script = runtime.create("Counter").unwrap();
+
+    script.set_field_value("Count", &7).unwrap();
+    assert_eq!(script.get_field_value::<i32>("Count").unwrap(), Some(7));
+
+    let position = Vector3 { x: 1.5, y: -2.0, z: 0.25 };
+    script.set_field_value("Position", &position).unwrap();
+    let mut value = Vector3::zeroed();
+    script.get_field_value_into("Position", &mut value).unwrap();
+    assert_eq!(value, position);
+
+    let count = runtime.field_accessor("Counter", "Count").unwrap();
+    count.set(&script, &3).unwrap();
+    let mut value = 0i32;
+    count.get_into(&script, &mut value).unwrap();
+    assert_eq!(value, 3);
+    assert_eq!(count.get::<i32>(&script).unwrap(), Some(3));
+}
+
+fn properties(app: &mut App) {
+    let runtime = app.world().resource::<Runtime>();
+    let script = runtime.create("Counter").unwrap();
+
+    assert_eq!(script.get_property_value::<f32>("Speed").unwrap(), Some(1.0));
+    script.set_property_value("Speed", &2.5f32).unwrap();
+    assert_eq!(script.get_property_value::<f32>("Speed").unwrap(), Some(2.5));
+
+    script.set_field_value("Count", &3).unwrap();
+    let mut doubled = 0i32;
+    script.get_property_value_into("Doubled", &mut doubled).unwrap();
+    assert_eq!(doubled, 6);
+
+    let speed = runtime.property_accessor("Counter", "Speed").unwrap();
+    speed.set(&script, &4.0f32).unwrap();
+    let mut value = 0f32;
+    speed.get_into(&script, &mut value).unwrap();
+    assert_eq!(value, 4.0);
+}
+
+fn errors(app: &mut App) {
+    let runtime = app.world().resource::<Runtime>();
+    let script = runtime.create("Counter").unwrap();
+    let faulty = runtime.create("Faulty").unwrap();
+
+    assert!(matches!(script.get_field_value::<i32>("Missing"), Err(Error::FieldNotFound)));
+    assert!(matches!(script.set_field_value("Limit", &1), Err(Error::ReadonlyField)));
+    assert!(matches!(script.get_property_value::<i32>("Missing"), Err(Error::PropertyNotFound)));
+    assert!(matches!(script.set_property_value("Doubled", &1), Err(Error::MissingSetter)));
+    assert!(matches!(script.get_field_value_into("Count", &mut 0u64), Err(Error::SizeMismatch)));
+
+    let add = runtime.get_method(&script, "Add", 1).unwrap().unwrap();
+    assert!(matches!(add.invoke(&1.0f32), Err(Error::ArgumentTypeMismatch { .. })));
+    assert!(matches!(add.invoke((&1, &2)), Err(Error::ArgumentCountMismatch { .. })));
+    // Only the scripts whose class's method doesn't take the arguments fail
+    let other = runtime.create("Counter").unwrap();
+    let failed = runtime.invoke_all([&script, &faulty, &other], "Add", &1.5f32).unwrap();
+    let positions = failed.iter().map(|(position, _)| *position).collect::<Vec<_>>();
+    assert_eq!(positions, [0, 2]);
+    assert!(failed.iter().all(|(_, error)| matches!(error, Error::ArgumentTypeMismatch { .. })));
+    assert_eq!(faulty.get_field_value::<f32>("Total").unwrap(), Some(1.5));
+
+    let add_all = runtime.get_method(&script, "AddAll", 1).unwrap().unwrap();
+    assert!(matches!(add_all.invoke([1, 2].as_slice()), Err(Error::ArgumentTypeMismatch { .. })));
+
+    // An object handle would be read as a C string
+    let rename = runtime.get_method(&script, "Rename", 1).unwrap().unwrap();
+    assert!(matches!(rename.invoke(&*faulty), Err(Error::ArgumentTypeMismatch { .. })));
+
+    let count = runtime.field_accessor("Counter", "Count").unwrap();
+    assert!(matches!(count.get::<i32>(&faulty), Err(Error::InvalidInstance)));
+    assert!(matches!(count.set(&script, &1.0f32), Err(Error::ArgumentTypeMismatch { .. })));
+    // Setting by name is checked against the member type like accessors
+    assert!(matches!(script.set_field_value("Count", &1.0f64), Err(Error::ArgumentTypeMismatch { .. })));
+    assert!(matches!(script.set_property_value("Speed", &1.0f64), Err(Error::ArgumentTypeMismatch { .. })));
+    assert!(matches!(script.set_field_value("Name", &*faulty), Err(Error::ArgumentTypeMismatch { .. })));
+    assert!(matches!(
+        runtime.set_static_field_value("Helper", "Answer", &1.0f32),
+        Err(Error::ArgumentTypeMismatch { .. })
+    ));
+    assert!(matches!(runtime.field_accessor("Counter", "Missing"), Err(Error::FieldNotFound)));
+    assert!(matches!(runtime.get_class("Missing"), Err(Error::ClassNotFound)));
+}
+
+#[derive(Resource, Default)]
+struct Failures(usize);
+
+fn tick(query: Query<&Script>, runtime: Res<Runtime>, mut failures: ResMut<Failures>) {
+    for (_, error) in runtime.invoke_all(query, "Tick", &0.016f32).unwrap() {
+        assert!(matches!(error, Error::InvocationFailed));
+        failures.0 += 1;
+    }
+}
+
+fn systems(app: &mut App) {
+    let runtime = app.world().resource::<Runtime>();
+    let (counter, faulty) = (runtime.create("Counter").unwrap(), runtime.create("Faulty").unwrap());
+    let counter = app.world_mut().spawn(counter).id();
+    app.world_mut().spawn(faulty);
+
+    app.init_resource::<Failures>().add_systems(Update, tick);
+    app.update();
+    app.update();
+
+    // Faulty throws every frame without stopping the counter from ticking
+    assert_eq!(app.world().resource::<Failures>().0, 2);
+    let script = app.world().get::<Script>(counter).unwrap();
+    assert_eq!(script.get_field_value::<i32>("Count").unwrap(), Some(2));
+}